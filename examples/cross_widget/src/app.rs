@@ -15,14 +15,33 @@ pub struct App {
     events: Vec<String>,
     debug: Vec<String>,
     paragraph_text: String,
+    switch_on: bool,
+    checkboxes: [bool; 3],
+    selected_radio: usize,
+    options_expanded: bool,
+    date: String,
+    time: String,
+    color_scheme: String,
+    pressed_at: Option<(i32, i32)>,
 }
 
 #[derive(Debug, Clone)]
 pub enum Msg {
     Click,
     ChangeText(String),
+    KeyDown(String),
+    LeaveText(String),
     Decrement,
     ParagraphChanged(String),
+    ToggleSwitch(bool),
+    ToggleCheckbox(usize, bool),
+    ToggleRadio(usize, bool),
+    ToggleOptions(bool),
+    ChangeDate(String),
+    ChangeTime(String),
+    ChangeColorScheme(String),
+    MouseDown(i32, i32),
+    DoubleClick,
 }
 
 impl App {
@@ -36,10 +55,48 @@ impl App {
             events: vec![],
             debug: vec![],
             paragraph_text: String::from("paragraph text"),
+            switch_on: false,
+            checkboxes: [true, false, false],
+            selected_radio: 0,
+            options_expanded: false,
+            date: String::from("2020-02-29"),
+            time: String::from("09:30"),
+            color_scheme: String::from("light"),
+            pressed_at: None,
         }
     }
 }
 
+impl App {
+    fn checkbox(&self, index: usize) -> Node<Msg> {
+        checkbox(vec![
+            label(format!("Checkbox{}", index + 1)),
+            checked(self.checkboxes[index]),
+            on_toggle(move |event: Event| match event {
+                Event::InputEvent(input) => Msg::ToggleCheckbox(index, input.value == "true"),
+                _ => {
+                    trace!("This is unexpected: {:#?}", event);
+                    panic!();
+                }
+            }),
+        ])
+    }
+
+    fn radio(&self, index: usize) -> Node<Msg> {
+        radio(vec![
+            label(format!("Radio{}", index + 1)),
+            checked(self.selected_radio == index),
+            on_toggle(move |event: Event| match event {
+                Event::InputEvent(input) => Msg::ToggleRadio(index, input.value == "true"),
+                _ => {
+                    trace!("This is unexpected: {:#?}", event);
+                    panic!();
+                }
+            }),
+        ])
+    }
+}
+
 impl Component<Msg> for App {
     fn update(&mut self, msg: Msg) {
         match msg {
@@ -48,35 +105,186 @@ impl Component<Msg> for App {
             Msg::ChangeText(txt) => {
                 self.text = txt;
             }
+            // enter submits and escape cancels the text
+            Msg::KeyDown(key) => match &*key {
+                "Enter" => self.click_count += 1,
+                "Escape" => self.text.clear(),
+                _ => (),
+            },
+            // the text is tidied up once the user is done with it
+            Msg::LeaveText(txt) => {
+                self.text = txt.trim().to_string();
+            }
             Msg::ParagraphChanged(txt) => {
                 self.paragraph_text = txt;
             }
+            Msg::ToggleSwitch(on) => {
+                self.switch_on = on;
+            }
+            Msg::ToggleCheckbox(index, checked) => {
+                self.checkboxes[index] = checked;
+            }
+            // a radio is also toggled off when another one is selected
+            Msg::ToggleRadio(index, checked) => {
+                if checked {
+                    self.selected_radio = index;
+                }
+            }
+            Msg::ToggleOptions(expanded) => {
+                self.options_expanded = expanded;
+            }
+            Msg::ChangeDate(date) => {
+                self.date = date;
+            }
+            Msg::ChangeTime(time) => {
+                self.time = time;
+            }
+            Msg::ChangeColorScheme(color_scheme) => {
+                self.color_scheme = color_scheme;
+            }
+            Msg::MouseDown(x, y) => {
+                self.pressed_at = Some((x, y));
+            }
+            // double clicking the paragraph starts counting again
+            Msg::DoubleClick => self.click_count = 0,
         }
     }
 
     fn view(&self) -> Node<Msg> {
-        column(
-            vec![],
+        window(
             vec![
+                title("Cross widget"),
+                width(800.0),
+                height(1000.0),
+                icon("applications-graphics"),
+                theme(Theme {
+                    accent: Some(Color::rgb(0x34, 0x65, 0xa4)),
+                    corner_radius: Some(4.0),
+                    ..Default::default()
+                }),
+                dark_theme(Theme {
+                    foreground: Some(Color::rgb(0xee, 0xee, 0xec)),
+                    background: Some(Color::rgb(0x2e, 0x34, 0x36)),
+                    accent: Some(Color::rgb(0x72, 0x9f, 0xcf)),
+                    corner_radius: Some(4.0),
+                    ..Default::default()
+                }),
+                on_color_scheme(|event: Event| match event {
+                    Event::InputEvent(input) => Msg::ChangeColorScheme(input.value),
+                    _ => {
+                        trace!("This is unexpected: {:#?}", event);
+                        panic!();
+                    }
+                }),
+            ],
+            vec![
+                toolbar(
+                    vec![],
+                    vec![
+                        tool_button(vec![
+                            icon("list-add"),
+                            label("Increment"),
+                            on_click(|_| Msg::Click),
+                        ]),
+                        tool_button(vec![
+                            icon("list-remove"),
+                            label("Decrement"),
+                            on_click(|_| Msg::Decrement),
+                        ]),
+                        tool_separator(),
+                        space(vec![]),
+                        tool_toggle(vec![
+                            label("Options"),
+                            value(self.options_expanded),
+                            on_toggle(|event: Event| match event {
+                                Event::InputEvent(input) => {
+                                    Msg::ToggleOptions(input.value == "true")
+                                }
+                                _ => {
+                                    trace!("This is unexpected: {:#?}", event);
+                                    panic!();
+                                }
+                            }),
+                        ]),
+                    ],
+                ),
                 column(
                     vec![],
                     vec![
                         paragraph(&self.debug.join("\n")),
-                        button(vec![on_click(|_| Msg::Decrement), label(&self.text)]),
+                        button(vec![
+                            on_click(|_| Msg::Decrement),
+                            label(&self.text),
+                            disabled(self.click_count == 0),
+                        ]),
                     ],
                 ),
                 button(vec![
                     on_click(|_| Msg::Click),
                     label(format!("Hello: {}", self.click_count)),
                 ]),
-                column(
-                    vec![],
+                expander(
+                    vec![
+                        label("Options"),
+                        expanded(self.options_expanded),
+                        on_toggle(|event: Event| match event {
+                            Event::InputEvent(input) => Msg::ToggleOptions(input.value == "true"),
+                            _ => {
+                                trace!("This is unexpected: {:#?}", event);
+                                panic!();
+                            }
+                        }),
+                    ],
+                    vec![column(
+                        vec![spacing(4.0), padding(Insets::symmetric(4.0, 16.0))],
+                        vec![
+                            self.checkbox(0),
+                            self.checkbox(1),
+                            self.checkbox(2),
+                            self.radio(0),
+                            self.radio(1),
+                            switch(vec![
+                                value(self.switch_on),
+                                on_toggle(|event: Event| match event {
+                                    Event::InputEvent(input) => {
+                                        Msg::ToggleSwitch(input.value == "true")
+                                    }
+                                    _ => {
+                                        trace!("This is unexpected: {:#?}", event);
+                                        panic!();
+                                    }
+                                }),
+                            ]),
+                        ],
+                    )],
+                ),
+                row(
+                    vec![
+                        spacing(8.0),
+                        alignment(Alignment::Center),
+                        cross_alignment(Align::Center),
+                    ],
                     vec![
-                        checkbox(vec![label("Checkbox1"), value(true)]),
-                        checkbox(vec![label("Checkbox2"), value(false)]),
-                        checkbox(vec![label("Checkbox3"), value(false)]),
-                        radio(vec![label("Radio1"), value(true)]),
-                        radio(vec![label("Radio2"), value(false)]),
+                        date_picker(vec![
+                            value(&self.date),
+                            on_change(|event: Event| match event {
+                                Event::InputEvent(input) => Msg::ChangeDate(input.value),
+                                _ => {
+                                    trace!("This is unexpected: {:#?}", event);
+                                    panic!();
+                                }
+                            }),
+                        ]),
+                        time_picker(vec![
+                            value(&self.time),
+                            on_change(|event: Event| match event {
+                                Event::InputEvent(input) => Msg::ChangeTime(input.value),
+                                _ => {
+                                    trace!("This is unexpected: {:#?}", event);
+                                    panic!();
+                                }
+                            }),
+                        ]),
                     ],
                 ),
                 row(vec![], {
@@ -86,6 +294,7 @@ impl Component<Msg> for App {
                 }),
                 text_input(vec![
                     value(self.events.join("\n")),
+                    focused(true),
                     on_input(|event: Event| match event {
                         Event::InputEvent(input) => Msg::ChangeText(input.value),
                         _ => {
@@ -93,6 +302,17 @@ impl Component<Msg> for App {
                             panic!();
                         }
                     }),
+                    on_key_down(|event: Event| match event {
+                        Event::KeyEvent(key_event) => Msg::KeyDown(key_event.key),
+                        _ => unreachable!(),
+                    }),
+                    on_blur(|event: Event| match event {
+                        Event::InputEvent(input) => Msg::LeaveText(input.value),
+                        _ => {
+                            trace!("This is unexpected: {:#?}", event);
+                            panic!();
+                        }
+                    }),
                 ]),
                 row(
                     vec![],
@@ -121,7 +341,41 @@ impl Component<Msg> for App {
                     }),
                     height(7.0),
                 ]),
-                textarea(vec![value(&self.paragraph_text)]),
+                textarea(vec![
+                    value(&self.paragraph_text),
+                    expand(true),
+                    on_mouse_down(|event: Event| match event {
+                        Event::MouseEvent(mouse) => {
+                            Msg::MouseDown(mouse.coordinate.offset_x, mouse.coordinate.offset_y)
+                        }
+                        _ => unreachable!(),
+                    }),
+                    on_double_click(|_| Msg::DoubleClick),
+                ]),
+                link(vec![
+                    label("sauron-native on github"),
+                    uri("https://github.com/ivanceras/sauron-native"),
+                ]),
+                separator(vec![]),
+                status_bar(
+                    vec![style(Style {
+                        background: Some(Color::rgb(0xee, 0xee, 0xec)),
+                        ..Default::default()
+                    })],
+                    vec![
+                        status_segment(&format!("Clicks: {}", self.click_count)),
+                        status_segment(if self.switch_on {
+                            "Switch: on"
+                        } else {
+                            "Switch: off"
+                        }),
+                        status_segment(&format!("Color scheme: {}", self.color_scheme)),
+                        status_segment(&match self.pressed_at {
+                            Some((x, y)) => format!("Pressed at: {}, {}", x, y),
+                            None => String::from("Pressed at: -"),
+                        }),
+                    ],
+                ),
             ],
         )
     }
@@ -1,49 +1,132 @@
 use super::Dispatch;
 use crate::{
     util,
-    widget::attribute::{find_callback, find_value},
+    widget::{
+        attribute::{find_callback, find_value},
+        date::{Date, Time},
+        focus,
+        key::{self, Modifiers},
+        layout::{
+            self, Align, Alignment, Fit, Flex, GridCell, Insets, SizeConstraints, Transition,
+        },
+        mouse, state,
+        style::{ColorScheme, Style, Theme},
+        window::{self, WindowConfig},
+    },
     AttribKey, Attribute, Backend, Component, Node, Patch, Widget,
 };
-use gdk_pixbuf::{PixbufLoader, PixbufLoaderExt};
+use gdk::{EventMask, EventType, ModifierType};
+use gdk_pixbuf::{InterpType, PixbufAnimation, PixbufAnimationExt, PixbufLoader, PixbufLoaderExt};
 use gio::{prelude::*, ApplicationFlags};
 use glib::Value;
 use gtk::{
-    prelude::*, Adjustment, Application, ApplicationWindow, Button, CheckButton, Container,
-    CssProvider, Entry, EntryBuffer, Image, IsA, Label, Orientation, Paned, RadioButton,
-    ScrolledWindow, StyleContext, TextBuffer, TextBufferExt, TextTagTable, TextView, TextViewExt,
-    WidgetExt, Window, WindowPosition, WindowType,
+    prelude::*, Adjustment, Application, ApplicationWindow, Button, Calendar, CheckButton,
+    Container, CssProvider, Entry, EntryBuffer, EntryIconPosition, EventBox, Expander, Grid, Image,
+    Inhibit, InputPurpose, IsA, Label, LinkButton, MenuButton, Orientation, Overlay, Paned,
+    PolicyType, Popover, RadioButton, ScrolledWindow, Separator, SeparatorToolItem, Settings,
+    SpinButton, Stack, StackTransitionType, Statusbar, StyleContext, Switch, TextBuffer,
+    TextBufferExt, TextTagTable, TextView, TextViewExt, ToggleToolButton, ToolButton, ToolItem,
+    Toolbar, WidgetExt, Window, WindowPosition, WindowType,
 };
 use image::ImageFormat;
 use log::*;
 use sauron_vdom::{
-    event::{InputEvent, MouseEvent},
+    event::{InputEvent, MouseButton, MouseEvent},
     AttribValue,
 };
-use std::{cell::RefCell, fmt::Debug, marker::PhantomData, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::Debug,
+    fs,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+thread_local! {
+    static ANIMATION_CACHE: RefCell<HashMap<PathBuf, PixbufAnimation>> =
+        RefCell::new(HashMap::new());
+    /// the css providers of the widgets styled from their attributes, by widget and concern,
+    /// so a patch replaces the previous style of that concern
+    static WIDGET_CSS: RefCell<HashMap<(gtk::Widget, &'static str), CssProvider>> =
+        RefCell::new(HashMap::new());
+    /// the css provider of the theme and the css it has, the provider is added to the screen once
+    static THEME_CSS: RefCell<Option<(CssProvider, String)>> = RefCell::new(None);
+    /// set while a view is built or patched, see applying_view
+    static APPLYING_VIEW: Cell<bool> = Cell::new(false);
+    /// the index of the node of each child of a grid, gtk lists the children of a grid
+    /// in the reverse order they are attached
+    static GRID_CHILD_INDEX: RefCell<HashMap<gtk::Widget, usize>> =
+        RefCell::new(HashMap::new());
+}
+
+/// the image formats gdk-pixbuf can load without converting them
+const PIXBUF_FORMATS: &[ImageFormat] = &[
+    ImageFormat::Png,
+    ImageFormat::Jpeg,
+    ImageFormat::Gif,
+    ImageFormat::Bmp,
+    ImageFormat::Tiff,
+    ImageFormat::Ico,
+];
 
 mod apply_patches;
 
+/// the style class of a space which grows to fill its container
+const SPACE_CLASS: &str = "space";
+
+/// the style class of a read only checkbox or radio, gtk buttons have no editable property
+const READ_ONLY_CLASS: &str = "read-only";
+
+/// used when the view doesn't specify an application id
+const DEFAULT_APPLICATION_ID: &str = "ivanceras.github.io.gtk";
+
 pub struct GtkBackend<APP, MSG>
 where
     MSG: 'static,
 {
     app: Rc<RefCell<APP>>,
     current_vdom: Rc<RefCell<Node<MSG>>>,
-    root_node: Rc<RefCell<Option<GtkWidget>>>,
+    windows: Rc<RefCell<Vec<AppWindow>>>,
     application: Application,
     _phantom_msg: PhantomData<MSG>,
 }
 
+/// A top level window and the widget tree it shows
+struct AppWindow {
+    /// matches the window to its node in the view
+    key: String,
+    window: ApplicationWindow,
+    root_node: GtkWidget,
+}
+
 pub(crate) enum GtkWidget {
     GBox(gtk::Box),
     Paned(Paned),
+    Grid(Grid),
+    Overlay(Overlay),
+    Stack(Stack),
+    Expander(Expander),
+    Toolbar(Toolbar),
+    StatusBar(Statusbar),
     Button(Button),
     Paragraph(TextView),
     TextInput(Entry),
     Checkbox(CheckButton),
+    Switch(Switch),
     Radio(RadioButton),
     Image(ScrolledWindow),
     TextView(ScrolledWindow),
+    DatePicker(MenuButton),
+    TimePicker(EventBox),
+    Link(LinkButton),
+    Separator(Separator),
+    Space(gtk::Box),
+    ToolButton(ToolButton),
+    ToolToggle(ToggleToolButton),
+    ToolSeparator(SeparatorToolItem),
+    StatusSegment(Label),
 }
 
 impl<APP, MSG> Clone for GtkBackend<APP, MSG> {
@@ -51,7 +134,7 @@ impl<APP, MSG> Clone for GtkBackend<APP, MSG> {
         GtkBackend {
             app: Rc::clone(&self.app),
             current_vdom: Rc::clone(&self.current_vdom),
-            root_node: Rc::clone(&self.root_node),
+            windows: Rc::clone(&self.windows),
             application: self.application.clone(),
             _phantom_msg: PhantomData,
         }
@@ -65,41 +148,19 @@ where
 {
     fn new(app: APP) -> Self {
         let current_vdom = app.view();
-        let root_vdom = app.view();
+        let application_id =
+            window::application_id(&current_vdom).unwrap_or(DEFAULT_APPLICATION_ID.to_string());
 
         if gtk::init().is_err() {
             println!("failed to initialize GTK Application");
         }
-        let root_widget: Option<GtkWidget> = None;
-        let mut backend = GtkBackend {
+        GtkBackend {
             app: Rc::new(RefCell::new(app)),
             current_vdom: Rc::new(RefCell::new(current_vdom)),
-            root_node: Rc::new(RefCell::new(root_widget)),
-            application: Application::new("ivanceras.github.io.gtk", ApplicationFlags::FLAGS_NONE)
+            windows: Rc::new(RefCell::new(vec![])),
+            application: Application::new(&*application_id, ApplicationFlags::FLAGS_NONE)
                 .expect("Failed to start app"),
             _phantom_msg: PhantomData,
-        };
-        let root_widget = Self::from_node_tree(&backend, root_vdom);
-        *backend.root_node.borrow_mut() = Some(root_widget);
-        backend
-    }
-
-    fn root_container(&self) -> Rc<Container> {
-        let root_widget = self.root_node.borrow();
-        if let Some(root_widget) = &*root_widget {
-            match &root_widget {
-                GtkWidget::GBox(gbox) => {
-                    let container: &Container = gbox.upcast_ref();
-                    Rc::new(container.clone())
-                }
-                GtkWidget::Paned(paned) => {
-                    let container: &Container = paned.upcast_ref();
-                    Rc::new(container.clone())
-                }
-                _ => panic!("expecting it to be a container"),
-            }
-        } else {
-            panic!("must have a root widget");
         }
     }
 
@@ -109,27 +170,165 @@ where
         MSG: Clone + Debug + 'static,
     {
         let self_clone = self.clone();
-        self.application.connect_activate(move |uiapp| {
-            let win = ApplicationWindow::new(uiapp);
-            let rc_win = Rc::new(win);
-            rc_win.set_default_size(800, 1000);
-            rc_win.set_icon_name(Some("applications-graphics"));
-            rc_win.set_title("Gtk backend");
-            self_clone.attach_root_widget(&rc_win);
-            rc_win.show_all();
+        self.application.connect_activate(move |_uiapp| {
+            self_clone.connect_color_scheme();
+            let window_nodes: Vec<(String, Node<MSG>)> =
+                window::window_nodes(&self_clone.current_vdom.borrow())
+                    .into_iter()
+                    .map(|(key, node)| (key, node.clone()))
+                    .collect();
+            for (key, window_node) in window_nodes {
+                self_clone.open_window(key, window_node);
+            }
         });
         self.application.run(&[]);
     }
 
-    fn attach_root_widget(&self, window: &Rc<ApplicationWindow>)
-    where
-        APP: Component<MSG> + 'static,
-        MSG: Clone + Debug + 'static,
-    {
-        if let Some(root_widget) = self.root_node.borrow().as_ref() {
-            if let Some(root_widget) = root_widget.as_widget() {
+    /// create a top level window showing the window node
+    fn open_window(&self, key: String, window_node: Node<MSG>) {
+        let window = ApplicationWindow::new(&self.application);
+        if let Some(element) = window_node.as_element_ref() {
+            if element.tag == Widget::Window {
+                set_window_attributes(&window, &element.attrs);
+                apply_theme(window.upcast_ref(), &element.attrs);
+                self.connect_close(&key, &window, &element.attrs);
+                self.emit_color_scheme_when_idle(&element.attrs);
+            }
+        }
+        let root_node = applying_view(|| {
+            let root_node = Self::from_node_tree(self, window_node);
+            if let Some(root_widget) = root_node.as_widget() {
                 window.add(root_widget);
             }
+            root_node
+        });
+        window.show_all();
+        self.windows.borrow_mut().push(AppWindow {
+            key,
+            window,
+            root_node,
+        });
+    }
+
+    /// The window is told about the color scheme once it is in the list of windows,
+    /// dispatching while it is being opened would patch the windows too early.
+    fn emit_color_scheme_when_idle(&self, attrs: &Vec<Attribute<MSG>>) {
+        if let Some(cb) = find_callback(AttribKey::ColorSchemeEvent, attrs).cloned() {
+            let program = self.clone();
+            gtk::idle_add(move || {
+                let color_scheme = system_color_scheme().as_str().to_string();
+                program.dispatch(cb.emit(InputEvent::new(color_scheme)));
+                gtk::Continue(false)
+            });
+        }
+    }
+
+    /// follow the changes of the settings which make up the color scheme
+    fn connect_color_scheme(&self) {
+        if let Some(settings) = Settings::get_default() {
+            let program = self.clone();
+            settings.connect_property_gtk_application_prefer_dark_theme_notify(move |_| {
+                program.color_scheme_changed();
+            });
+            let program = self.clone();
+            settings.connect_property_gtk_theme_name_notify(move |_| {
+                program.color_scheme_changed();
+            });
+        }
+    }
+
+    /// switch the theme of the windows and tell the windows listening to the color scheme
+    fn color_scheme_changed(&self) {
+        let color_scheme = system_color_scheme();
+        let window_nodes: Vec<(String, Node<MSG>)> =
+            window::window_nodes(&self.current_vdom.borrow())
+                .into_iter()
+                .map(|(key, node)| (key, node.clone()))
+                .collect();
+        for (key, window_node) in window_nodes {
+            let attrs = window_node.get_attributes();
+            let window = self
+                .windows
+                .borrow()
+                .iter()
+                .find(|app_window| app_window.key == key)
+                .map(|app_window| app_window.window.clone());
+            if let Some(window) = window {
+                apply_theme(window.upcast_ref(), &attrs);
+            }
+            if let Some(cb) = find_callback(AttribKey::ColorSchemeEvent, &attrs) {
+                let msg = cb.emit(InputEvent::new(color_scheme.as_str().to_string()));
+                self.dispatch(msg);
+            }
+        }
+    }
+
+    /// When the window has a close listener, the window is only closed once it is removed
+    /// from the view, closing it emits the listener instead. A window without a close listener
+    /// is closed right away and no longer patched, closing the last window quits the application.
+    fn connect_close(&self, key: &str, window: &ApplicationWindow, attrs: &Vec<Attribute<MSG>>) {
+        let close_listener = find_callback(AttribKey::CloseEvent, attrs).cloned();
+        let program = self.clone();
+        let key = key.to_string();
+        window.connect_delete_event(move |_window, _event| {
+            if let Some(cb) = &close_listener {
+                let msg = cb.emit(MouseEvent::default());
+                program.dispatch(msg);
+                return Inhibit(true);
+            }
+            program
+                .windows
+                .borrow_mut()
+                .retain(|app_window| app_window.key != key);
+            if program.windows.borrow().is_empty() {
+                program.application.quit();
+            }
+            Inhibit(false)
+        });
+    }
+
+    /// patch the windows that are in both views, open the windows that
+    /// are added and close the windows that are removed
+    fn update_windows(&self, old_view: &Node<MSG>, new_view: &Node<MSG>) {
+        let old_windows = window::window_nodes(old_view);
+        let new_windows = window::window_nodes(new_view);
+
+        self.windows.borrow_mut().retain(|app_window| {
+            let in_view = new_windows.iter().any(|(key, _)| *key == app_window.key);
+            if !in_view {
+                app_window.window.destroy();
+            }
+            in_view
+        });
+
+        for (key, new_node) in new_windows {
+            match old_windows.iter().find(|(old_key, _)| *old_key == key) {
+                Some((_, old_node)) => {
+                    let app_window = self
+                        .windows
+                        .borrow()
+                        .iter()
+                        .find(|app_window| app_window.key == key)
+                        .map(|app_window| (app_window.window.clone(), app_window.root_container()));
+                    if let Some((window, root_container)) = app_window {
+                        let diff = sauron_vdom::diff_with_key(old_node, new_node, &AttribKey::Key);
+                        applying_view(|| {
+                            apply_patches::apply_patches(self, &root_container, old_node, &diff)
+                        });
+                        let attrs = new_node.get_attributes();
+                        // the attributes the new node doesn't have are reset, the patches
+                        // only have the attributes which are added or changed
+                        if WindowConfig::from_attrs(&attrs)
+                            != WindowConfig::from_attrs(&old_node.get_attributes())
+                        {
+                            set_window_attributes(&window, &attrs);
+                        }
+                        // the theme depends on both the theme and the dark theme of the window
+                        apply_theme(window.upcast_ref(), &attrs);
+                    }
+                }
+                None => self.open_window(key, new_node.clone()),
+            }
         }
     }
 
@@ -141,12 +340,41 @@ where
         match widget_node {
             crate::Node::Element(element) => {
                 let mut gtk_widget = from_node(program, &element.tag, &element.attrs);
+                if let Some(widget) = gtk_widget.as_widget() {
+                    focus_when_shown(widget, &element.attrs);
+                    set_size_constraints(widget, &element.attrs);
+                    set_style(widget, &element.attrs);
+                    set_interaction_state(widget, &element.attrs);
+                    set_visibility(widget, &element.attrs);
+                    connect_key_events(program, widget, &element.attrs);
+                    connect_mouse_events(program, widget, &element.attrs);
+                    connect_focus_events(program, widget, &element.attrs);
+                }
                 let mut children = vec![];
                 for child in element.children {
+                    // the child attributes is needed by the container to place the child
+                    let child_attrs = child.get_attributes();
                     let gtk_child = Self::from_node_tree(program, child);
-                    children.push(gtk_child);
+                    children.push((gtk_child, child_attrs));
                 }
+                let tab_order: Vec<(Option<u32>, gtk::Widget)> = children
+                    .iter()
+                    .filter_map(|(child, child_attrs)| {
+                        child
+                            .as_widget()
+                            .map(|widget| (focus::tab_index(child_attrs), widget.clone()))
+                    })
+                    .collect();
                 gtk_widget.add_children(children);
+                if tab_order.iter().any(|(tab_index, _)| tab_index.is_some()) {
+                    if let Some(container) = gtk_widget.as_container() {
+                        set_tab_order(container, tab_order);
+                    }
+                }
+                // the visible child of a stack can only be set once the children are added
+                if let GtkWidget::Stack(stack) = &gtk_widget {
+                    stack.set_visible_child_name(&layout::visible_page(&element.attrs));
+                }
                 gtk_widget
             }
             crate::Node::Text(txt) => {
@@ -167,14 +395,28 @@ where
     DSP: Clone + Dispatch<MSG> + 'static,
 {
     match widget {
+        // the windows of an application are created in open_window,
+        // an application nested in the view is just a container
+        Widget::Application => {
+            let vbox = gtk::Box::new(Orientation::Vertical, 0);
+            GtkWidget::GBox(vbox)
+        }
+        // the attributes of the window are applied to the ApplicationWindow,
+        // this box holds the content of the window
+        Widget::Window => {
+            let vbox = gtk::Box::new(Orientation::Vertical, 0);
+            GtkWidget::GBox(vbox)
+        }
         // vbox can have many children
         Widget::Vbox => {
             let vbox = gtk::Box::new(Orientation::Vertical, 0);
+            set_box_layout(&vbox, attrs);
             GtkWidget::GBox(vbox)
         }
         // hbox can have many children
         Widget::Hbox => {
             let hbox = gtk::Box::new(Orientation::Horizontal, 0);
+            set_box_layout(&hbox, attrs);
             GtkWidget::GBox(hbox)
         }
         // paned has only 2 children
@@ -186,6 +428,32 @@ where
             let vpane = Paned::new(Orientation::Vertical);
             GtkWidget::Paned(vpane)
         }
+        // children are attached to the cell declared in their attributes
+        Widget::Grid => {
+            let grid = Grid::new();
+            GtkWidget::Grid(grid)
+        }
+        // the first child is the main child, the rest are layered on top of it
+        Widget::Stack => {
+            let overlay = Overlay::new();
+            GtkWidget::Overlay(overlay)
+        }
+        // children are added by name, only the visible child is shown
+        Widget::Pages => {
+            let stack = Stack::new();
+            stack.set_transition_type(stack_transition_type(Transition::find(&attrs)));
+            GtkWidget::Stack(stack)
+        }
+        // the items are added with add_to_container
+        Widget::Toolbar => {
+            let toolbar = Toolbar::new();
+            GtkWidget::Toolbar(toolbar)
+        }
+        // the segments are placed after the message area of the statusbar, which is not used
+        Widget::StatusBar => {
+            let statusbar = Statusbar::new();
+            GtkWidget::StatusBar(statusbar)
+        }
         Widget::Button => {
             let label = find_value(AttribKey::Label, &attrs)
                 .map(|v| v.to_string())
@@ -195,14 +463,119 @@ where
             if let Some(cb) = find_callback(AttribKey::ClickEvent, &attrs) {
                 let cb_clone = cb.clone();
                 let program_clone = program.clone();
-                btn.connect_clicked(move |_| {
-                    let mouse_event = MouseEvent::default();
+                btn.connect_clicked(move |btn| {
+                    let mouse_event = click_event(btn.upcast_ref());
                     let msg = cb_clone.emit(mouse_event);
                     program_clone.dispatch(msg);
                 });
             }
             GtkWidget::Button(btn)
         }
+        // a link with a click listener emits it instead of opening the uri
+        Widget::Link => {
+            let label = find_value(AttribKey::Label, &attrs)
+                .map(|v| v.to_string())
+                .unwrap_or(String::new());
+            let uri = find_value(AttribKey::Uri, &attrs)
+                .map(|v| v.to_string())
+                .unwrap_or(String::new());
+
+            let link = LinkButton::new_with_label(&uri, Some(&label));
+            if let Some(cb) = find_callback(AttribKey::ClickEvent, &attrs) {
+                let cb_clone = cb.clone();
+                let program_clone = program.clone();
+                link.connect_activate_link(move |link| {
+                    let mouse_event = click_event(link.upcast_ref());
+                    let msg = cb_clone.emit(mouse_event);
+                    program_clone.dispatch(msg);
+                    Inhibit(true)
+                });
+            }
+            GtkWidget::Link(link)
+        }
+        Widget::Separator => {
+            let separator = Separator::new(gtk_orientation(layout::Orientation::find(&attrs)));
+            GtkWidget::Separator(separator)
+        }
+        // a flexible space is marked with a style class, so it is packed
+        // to expand when it is added to a box
+        Widget::Space => {
+            let space = gtk::Box::new(Orientation::Horizontal, 0);
+            let size = |key| {
+                find_value(key, &attrs)
+                    .map(|v| v.as_f64())
+                    .flatten()
+                    .map(|v| v as i32)
+                    .unwrap_or(-1)
+            };
+            space.set_size_request(size(AttribKey::Width), size(AttribKey::Height));
+            if layout::is_flexible_space(&attrs) {
+                space.get_style_context().add_class(SPACE_CLASS);
+            }
+            GtkWidget::Space(space)
+        }
+        Widget::ToolButton => {
+            let label = find_value(AttribKey::Label, &attrs)
+                .map(|v| v.to_string())
+                .unwrap_or(String::new());
+
+            let tool_button = ToolButton::new(None::<&gtk::Widget>, Some(&label));
+            if let Some(icon) = find_value(AttribKey::Icon, &attrs) {
+                set_tool_icon(&tool_button, &icon.to_string());
+            }
+            if let Some(cb) = find_callback(AttribKey::ClickEvent, &attrs) {
+                let cb_clone = cb.clone();
+                let program_clone = program.clone();
+                tool_button.connect_clicked(move |tool_button| {
+                    let mouse_event = click_event(tool_button.upcast_ref());
+                    let msg = cb_clone.emit(mouse_event);
+                    program_clone.dispatch(msg);
+                });
+            }
+            GtkWidget::ToolButton(tool_button)
+        }
+        Widget::ToolToggle => {
+            let label = find_value(AttribKey::Label, &attrs)
+                .map(|v| v.to_string())
+                .unwrap_or(String::new());
+            let value = find_value(AttribKey::Value, &attrs)
+                .map(|v| v.as_bool())
+                .flatten()
+                .unwrap_or(false);
+
+            let tool_toggle = ToggleToolButton::new();
+            tool_toggle.set_label(Some(&label));
+            tool_toggle.set_active(value);
+            if let Some(icon) = find_value(AttribKey::Icon, &attrs) {
+                set_tool_icon(&tool_toggle, &icon.to_string());
+            }
+            if let Some(cb) = find_callback(AttribKey::ToggleEvent, &attrs) {
+                let cb_clone = cb.clone();
+                let program_clone = program.clone();
+                tool_toggle.connect_toggled(move |tool_toggle| {
+                    if is_applying_view() {
+                        return;
+                    }
+                    let input_event = InputEvent::new(tool_toggle.get_active().to_string());
+                    let msg = cb_clone.emit(input_event);
+                    program_clone.dispatch(msg);
+                });
+            }
+            GtkWidget::ToolToggle(tool_toggle)
+        }
+        Widget::ToolSeparator => {
+            let separator = SeparatorToolItem::new();
+            GtkWidget::ToolSeparator(separator)
+        }
+        Widget::StatusSegment => {
+            let txt = find_value(AttribKey::Value, &attrs)
+                .map(|v| v.to_string())
+                .unwrap_or(String::new());
+            let label = Label::new(Some(&*txt));
+            label.set_margin_start(6);
+            label.set_margin_end(6);
+            GtkWidget::StatusSegment(label)
+        }
         Widget::Paragraph => {
             let buffer = TextBuffer::new(None::<&TextTagTable>);
             let text_view = TextView::new_with_buffer(&buffer);
@@ -220,9 +593,28 @@ where
                 .map(|v| v.to_string())
                 .unwrap_or(String::new());
 
+            let password = find_value(AttribKey::Password, &attrs)
+                .map(|v| v.as_bool())
+                .flatten()
+                .unwrap_or(false);
+
+            let reveal = find_value(AttribKey::Reveal, &attrs)
+                .map(|v| v.as_bool())
+                .flatten()
+                .unwrap_or(false);
+
             let buffer = EntryBuffer::new(Some(&*value));
             let entry = Entry::new_with_buffer(&buffer);
 
+            set_password_mode(&entry, password, reveal);
+            entry.connect_icon_press(|entry, position, _event| {
+                if position == EntryIconPosition::Secondary
+                    && entry.get_input_purpose() == InputPurpose::Password
+                {
+                    entry.set_visibility(!entry.get_visibility());
+                }
+            });
+
             if let Some(cb) = find_callback(AttribKey::InputEvent, &attrs) {
                 let cb_clone = cb.clone();
                 let program_clone = program.clone();
@@ -239,71 +631,146 @@ where
                 .map(|v| v.to_string())
                 .unwrap_or(String::new());
 
-            let value = find_value(AttribKey::Value, &attrs)
-                .map(|v| v.as_bool())
-                .flatten()
-                .unwrap_or(false);
+            let checked = state::is_checked(&attrs);
 
             let cb = CheckButton::new_with_label(&label);
-            cb.set_property("active", &value);
+            cb.set_property("active", &checked);
+            ignore_when_read_only(cb.upcast_ref());
+            connect_toggled(program, cb.upcast_ref(), &attrs);
             GtkWidget::Checkbox(cb)
         }
-        Widget::Radio => {
+        // the children are added to a box inside the expander since it can only have one child
+        Widget::Expander => {
             let label = find_value(AttribKey::Label, &attrs)
                 .map(|v| v.to_string())
                 .unwrap_or(String::new());
-
-            let value = find_value(AttribKey::Value, &attrs)
+            let expanded = find_value(AttribKey::Expanded, &attrs)
                 .map(|v| v.as_bool())
                 .flatten()
                 .unwrap_or(false);
-            let rb = RadioButton::new_with_label(&label);
-            rb.set_property("active", &value);
-            GtkWidget::Radio(rb)
+
+            let expander = Expander::new(Some(&label));
+            expander.set_expanded(expanded);
+            expander.add(&gtk::Box::new(Orientation::Vertical, 0));
+
+            if let Some(cb) = find_callback(AttribKey::ToggleEvent, &attrs) {
+                let cb_clone = cb.clone();
+                let program_clone = program.clone();
+                expander.connect_property_expanded_notify(move |expander| {
+                    if is_applying_view() {
+                        return;
+                    }
+                    let input_event = InputEvent::new(expander.get_expanded().to_string());
+                    let msg = cb_clone.emit(input_event);
+                    program_clone.dispatch(msg);
+                });
+            }
+            GtkWidget::Expander(expander)
         }
-        Widget::Image => {
-            let empty = vec![];
-            let bytes = find_value(AttribKey::Data, &attrs)
-                .map(|v| v.as_bytes())
-                .flatten()
-                .unwrap_or(&empty);
-            let image = Image::new();
-            let mime = util::image_mime_type(&bytes).expect("unsupported have mime type");
-            let pixbuf_loader = PixbufLoader::new_with_mime_type(mime).expect("error loader");
-            pixbuf_loader
-                .write(&bytes)
-                .expect("Unable to write svg data into pixbuf_loader");
+        // the calendar is in a popover of a button showing the picked date
+        Widget::DatePicker => {
+            let date = Date::find(&attrs);
+            let menu_button = MenuButton::new();
+            menu_button.set_label(&date_label(date));
 
-            pixbuf_loader.close().expect("error creating pixbuf");
+            let calendar = Calendar::new();
+            if let Some(date) = date {
+                select_date(&calendar, date);
+            }
+            let popover = Popover::new(Some(&menu_button));
+            popover.add(&calendar);
+            calendar.show();
+            menu_button.set_popover(Some(&popover));
 
-            let pixbuf = pixbuf_loader.get_pixbuf();
+            if let Some(cb) = find_callback(AttribKey::ChangeEvent, &attrs) {
+                let cb_clone = cb.clone();
+                let program_clone = program.clone();
+                calendar.connect_day_selected(move |calendar| {
+                    if is_applying_view() {
+                        return;
+                    }
+                    let input_event = InputEvent::new(calendar_date(calendar).to_string());
+                    let msg = cb_clone.emit(input_event);
+                    program_clone.dispatch(msg);
+                });
+            }
+            calendar.connect_day_selected_double_click(move |_| popover.popdown());
+            GtkWidget::DatePicker(menu_button)
+        }
+        // gtk has no time picker, the hour and the minute are spin buttons
+        // in an event box, so the patches don't mistake them for child nodes
+        Widget::TimePicker => {
+            let time = Time::find(&attrs).unwrap_or_default();
+            let event_box = EventBox::new();
+            let hbox = gtk::Box::new(Orientation::Horizontal, 2);
+            event_box.add(&hbox);
+            let hour = time_spin_button(23.0, time.hour);
+            let minute = time_spin_button(59.0, time.minute);
+            hbox.add(&hour);
+            hbox.add(&Label::new(Some(":")));
+            hbox.add(&minute);
 
-            image.set_from_pixbuf(Some(&pixbuf.expect("error in pixbuf_loader")));
-            let scroll_view = ScrolledWindow::new(None::<&Adjustment>, None::<&Adjustment>);
-            scroll_view.add(&image);
-            GtkWidget::Image(scroll_view)
+            if let Some(cb) = find_callback(AttribKey::ChangeEvent, &attrs) {
+                for spin_button in &[&hour, &minute] {
+                    let cb_clone = cb.clone();
+                    let program_clone = program.clone();
+                    let hour = hour.clone();
+                    let minute = minute.clone();
+                    spin_button.connect_value_changed(move |_| {
+                        // a patched time sets the hour then the minute, the time
+                        // in between is neither the old one nor the new one
+                        if is_applying_view() {
+                            return;
+                        }
+                        let time = Time {
+                            hour: hour.get_value_as_int() as u32,
+                            minute: minute.get_value_as_int() as u32,
+                        };
+                        let input_event = InputEvent::new(time.to_string());
+                        let msg = cb_clone.emit(input_event);
+                        program_clone.dispatch(msg);
+                    });
+                }
+            }
+            GtkWidget::TimePicker(event_box)
         }
-        Widget::Svg => {
-            let empty = vec![];
-            let bytes = find_value(AttribKey::Data, &attrs)
-                .map(|v| v.as_bytes())
+        Widget::Switch => {
+            let value = find_value(AttribKey::Value, &attrs)
+                .map(|v| v.as_bool())
                 .flatten()
-                .unwrap_or(&empty);
-            let image = Image::new();
-            let pixbuf_loader =
-                PixbufLoader::new_with_mime_type("image/svg+xml").expect("error loader");
-            pixbuf_loader
-                .write(bytes)
-                .expect("Unable to write svg data into pixbuf_loader");
+                .unwrap_or(false);
 
-            pixbuf_loader.close().expect("error creating pixbuf");
+            let switch = Switch::new();
+            switch.set_active(value);
 
-            let pixbuf = pixbuf_loader.get_pixbuf();
+            if let Some(cb) = find_callback(AttribKey::ToggleEvent, &attrs) {
+                let cb_clone = cb.clone();
+                let program_clone = program.clone();
+                switch.connect_property_active_notify(move |switch| {
+                    if is_applying_view() {
+                        return;
+                    }
+                    let input_event = InputEvent::new(switch.get_active().to_string());
+                    let msg = cb_clone.emit(input_event);
+                    program_clone.dispatch(msg);
+                });
+            }
+            GtkWidget::Switch(switch)
+        }
+        Widget::Radio => {
+            let label = find_value(AttribKey::Label, &attrs)
+                .map(|v| v.to_string())
+                .unwrap_or(String::new());
 
-            image.set_from_pixbuf(Some(&pixbuf.expect("error in pixbuf_loader")));
-            let scroll_view = ScrolledWindow::new(None::<&Adjustment>, None::<&Adjustment>);
-            scroll_view.add(&image);
-            GtkWidget::Image(scroll_view)
+            let checked = state::is_checked(&attrs);
+            let rb = RadioButton::new_with_label(&label);
+            rb.set_property("active", &checked);
+            ignore_when_read_only(rb.upcast_ref());
+            connect_toggled(program, rb.upcast_ref(), &attrs);
+            GtkWidget::Radio(rb)
+        }
+        Widget::Image | Widget::Svg => {
+            GtkWidget::Image(fitted_image(image_animation(widget, attrs), attrs))
         }
         Widget::TextArea => {
             let value = find_value(AttribKey::Value, &attrs)
@@ -314,7 +781,6 @@ where
             buffer.set_text(&value);
 
             if let Some(cb) = find_callback(AttribKey::InputEvent, &attrs) {
-                println!("textarea has a callback..");
                 let cb_clone = cb.clone();
                 let program_clone = program.clone();
                 buffer.connect_changed(move |buffer| {
@@ -363,13 +829,21 @@ where
         let new_view = self.app.borrow().view();
         {
             let current_vdom = self.current_vdom.borrow();
-            let diff = sauron_vdom::diff_with_key(&current_vdom, &new_view, &AttribKey::Key);
-            apply_patches::apply_patches(self, &self.root_container(), &diff);
+            self.update_windows(&current_vdom, &new_view);
         }
         *self.current_vdom.borrow_mut() = new_view;
     }
 }
 
+impl AppWindow {
+    fn root_container(&self) -> Container {
+        match self.root_node.as_container() {
+            Some(container) => container.clone(),
+            None => panic!("expecting it to be a container"),
+        }
+    }
+}
+
 impl GtkWidget {
     fn as_container(&self) -> Option<&Container> {
         match self {
@@ -381,6 +855,30 @@ impl GtkWidget {
                 let container: &Container = paned.upcast_ref();
                 Some(container)
             }
+            GtkWidget::Grid(grid) => {
+                let container: &Container = grid.upcast_ref();
+                Some(container)
+            }
+            GtkWidget::Overlay(overlay) => {
+                let container: &Container = overlay.upcast_ref();
+                Some(container)
+            }
+            GtkWidget::Stack(stack) => {
+                let container: &Container = stack.upcast_ref();
+                Some(container)
+            }
+            GtkWidget::Expander(expander) => {
+                let container: &Container = expander.upcast_ref();
+                Some(container)
+            }
+            GtkWidget::Toolbar(toolbar) => {
+                let container: &Container = toolbar.upcast_ref();
+                Some(container)
+            }
+            GtkWidget::StatusBar(statusbar) => {
+                let container: &Container = statusbar.upcast_ref();
+                Some(container)
+            }
             _ => None,
         }
     }
@@ -399,6 +897,30 @@ impl GtkWidget {
                 let widget: &gtk::Widget = paned.upcast_ref();
                 Some(widget)
             }
+            GtkWidget::Grid(grid) => {
+                let widget: &gtk::Widget = grid.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Overlay(overlay) => {
+                let widget: &gtk::Widget = overlay.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Stack(stack) => {
+                let widget: &gtk::Widget = stack.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Expander(expander) => {
+                let widget: &gtk::Widget = expander.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Toolbar(toolbar) => {
+                let widget: &gtk::Widget = toolbar.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::StatusBar(statusbar) => {
+                let widget: &gtk::Widget = statusbar.upcast_ref();
+                Some(widget)
+            }
             GtkWidget::Paragraph(text_view) => {
                 let widget: &gtk::Widget = text_view.upcast_ref();
                 Some(widget)
@@ -411,6 +933,10 @@ impl GtkWidget {
                 let widget: &gtk::Widget = checkbox.upcast_ref();
                 Some(widget)
             }
+            GtkWidget::Switch(switch) => {
+                let widget: &gtk::Widget = switch.upcast_ref();
+                Some(widget)
+            }
             GtkWidget::Radio(radio) => {
                 let widget: &gtk::Widget = radio.upcast_ref();
                 Some(widget)
@@ -423,10 +949,49 @@ impl GtkWidget {
                 let widget: &gtk::Widget = text_view.upcast_ref();
                 Some(widget)
             }
+            GtkWidget::DatePicker(menu_button) => {
+                let widget: &gtk::Widget = menu_button.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::TimePicker(event_box) => {
+                let widget: &gtk::Widget = event_box.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Link(link) => {
+                let widget: &gtk::Widget = link.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Separator(separator) => {
+                let widget: &gtk::Widget = separator.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Space(space) => {
+                let widget: &gtk::Widget = space.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::ToolButton(tool_button) => {
+                let widget: &gtk::Widget = tool_button.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::ToolToggle(tool_toggle) => {
+                let widget: &gtk::Widget = tool_toggle.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::ToolSeparator(separator) => {
+                let widget: &gtk::Widget = separator.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::StatusSegment(label) => {
+                let widget: &gtk::Widget = label.upcast_ref();
+                Some(widget)
+            }
         }
     }
 
-    fn add_children(&self, children: Vec<GtkWidget>) {
+    fn add_children<MSG>(&self, children: Vec<(GtkWidget, Vec<Attribute<MSG>>)>)
+    where
+        MSG: 'static,
+    {
         match self {
             GtkWidget::Paned(paned) => {
                 if children.len() != 2 {
@@ -435,25 +1000,1255 @@ impl GtkWidget {
                 if children.len() > 2 {
                     warn!("pane children excess of 2 is ignored");
                 }
-                if let Some(child1) = children.get(0).map(|c| c.as_widget()).flatten() {
+                if let Some(child1) = children.get(0).map(|(c, _)| c.as_widget()).flatten() {
                     paned.pack1(child1, true, true);
                     child1.set_size_request(200, -1);
                 }
-                if let Some(child2) = children.get(1).map(|c| c.as_widget()).flatten() {
+                if let Some(child2) = children.get(1).map(|(c, _)| c.as_widget()).flatten() {
                     paned.pack2(child2, true, true);
                     child2.set_size_request(100, -1);
                 }
             }
             GtkWidget::GBox(container) => {
-                for child in children {
+                for (index, (child, child_attrs)) in children.iter().enumerate() {
                     if let Some(child_widget) = child.as_widget() {
-                        container.add(child_widget);
+                        add_to_container(container.upcast_ref(), index, child_widget, child_attrs);
                     } else {
                         println!("was not able to add child widget: {:?}", child.as_widget());
                     }
                 }
             }
+            GtkWidget::Expander(expander) => {
+                let content = expander
+                    .get_child()
+                    .map(|content| content.downcast::<gtk::Box>().ok())
+                    .flatten()
+                    .expect("must have a box");
+                for (child, _child_attrs) in children {
+                    if let Some(child_widget) = child.as_widget() {
+                        content.add(child_widget);
+                    }
+                }
+            }
+            GtkWidget::StatusBar(statusbar) => {
+                for (child, _child_attrs) in children {
+                    if let Some(child_widget) = child.as_widget() {
+                        statusbar.pack_start(child_widget, false, false, 0);
+                    }
+                }
+            }
+            GtkWidget::Grid(_)
+            | GtkWidget::Overlay(_)
+            | GtkWidget::Stack(_)
+            | GtkWidget::Toolbar(_) => {
+                let container = self.as_container().expect("must be a container");
+                for (index, (child, child_attrs)) in children.iter().enumerate() {
+                    if let Some(child_widget) = child.as_widget() {
+                        add_to_container(container, index, child_widget, child_attrs);
+                    }
+                }
+            }
             _ => (),
         }
     }
 }
+
+/// Apply the window attributes, used when the window is created and when the attributes
+/// of the window node changed. The attributes the node doesn't have are set to the defaults
+/// of gtk, so an attribute removed from the view doesn't stay on the window.
+pub(crate) fn set_window_attributes<MSG, W>(window: &W, attrs: &Vec<Attribute<MSG>>)
+where
+    MSG: 'static,
+    W: IsA<gtk::Window>,
+{
+    let config = WindowConfig::from_attrs(attrs);
+    window.set_title(config.title.as_deref().unwrap_or(""));
+    // -1 leaves the size to the content of the window
+    window.set_default_size(
+        config.width.map(|w| w as i32).unwrap_or(-1),
+        config.height.map(|h| h as i32).unwrap_or(-1),
+    );
+    window.set_size_request(
+        config.min_width.map(|w| w as i32).unwrap_or(-1),
+        config.min_height.map(|h| h as i32).unwrap_or(-1),
+    );
+    window.set_resizable(config.resizable.unwrap_or(true));
+    window.set_icon(None);
+    window.set_icon_name(None);
+    if let Some(icon) = &config.icon {
+        if Path::new(icon).is_file() {
+            if let Err(e) = window.set_icon_from_file(icon) {
+                error!("unable to load window icon {}: {}", icon, e);
+            }
+        } else {
+            window.set_icon_name(Some(icon));
+        }
+    }
+}
+
+/// Take the keyboard focus once the widget is shown when it has the focused attribute,
+/// a widget can only take the focus once it is inside a window.
+pub(crate) fn focus_when_shown<MSG>(widget: &gtk::Widget, attrs: &Vec<Attribute<MSG>>)
+where
+    MSG: 'static,
+{
+    if focus::is_focused(attrs) {
+        let pending = Rc::new(Cell::new(true));
+        widget.connect_map(move |widget| {
+            if pending.replace(false) {
+                grab_focus(widget);
+            }
+        });
+    }
+}
+
+/// Mask the text of a password entry and show the icon which reveals it, or show
+/// the text of an entry which is no longer a password. Gtk refuses to copy or cut
+/// the text of an entry that is not visible.
+pub(crate) fn set_password_mode(entry: &Entry, password: bool, reveal: bool) {
+    entry.set_visibility(!password);
+    entry.set_input_purpose(if password {
+        InputPurpose::Password
+    } else {
+        InputPurpose::FreeForm
+    });
+    let icon = if password && reveal {
+        Some("view-reveal-symbolic")
+    } else {
+        None
+    };
+    entry.set_icon_from_icon_name(EntryIconPosition::Secondary, icon);
+}
+
+/// the text view of a textarea, which is put in a scrolled window
+fn text_view_of(widget: &gtk::Widget) -> Option<TextView> {
+    widget
+        .downcast_ref::<ScrolledWindow>()
+        .map(|scroll_view| scroll_view.get_child())
+        .flatten()
+        .map(|child| child.downcast::<TextView>().ok())
+        .flatten()
+}
+
+/// Make the widget insensitive or its value read only. Only the states in the attributes
+/// are changed, so a patch of one state leaves the other one as it is.
+pub(crate) fn set_interaction_state<MSG>(widget: &gtk::Widget, attrs: &Vec<Attribute<MSG>>)
+where
+    MSG: 'static,
+{
+    if attrs.iter().any(|att| att.name == AttribKey::Disabled) {
+        widget.set_sensitive(!state::is_disabled(attrs));
+    }
+    if attrs.iter().any(|att| att.name == AttribKey::ReadOnly) {
+        let read_only = state::is_read_only(attrs);
+        if let Some(entry) = widget.downcast_ref::<Entry>() {
+            entry.set_editable(!read_only);
+        } else if let Some(text_view) = text_view_of(widget) {
+            text_view.set_editable(!read_only);
+            text_view.set_cursor_visible(!read_only);
+        } else if widget.is::<CheckButton>() {
+            let style_context = widget.get_style_context();
+            if read_only {
+                style_context.add_class(READ_ONLY_CLASS);
+            } else {
+                style_context.remove_class(READ_ONLY_CLASS);
+            }
+        }
+    }
+}
+
+/// Hide the widget while it stays in its container, so it keeps its state.
+/// A hidden widget is passed over by show_all, once it is visible again it is
+/// shown together with its children.
+pub(crate) fn set_visibility<MSG>(widget: &gtk::Widget, attrs: &Vec<Attribute<MSG>>)
+where
+    MSG: 'static,
+{
+    if attrs.iter().any(|att| att.name == AttribKey::Visible) {
+        let visible = state::is_visible(attrs);
+        widget.set_no_show_all(!visible);
+        if visible {
+            widget.show_all();
+        } else {
+            widget.hide();
+        }
+    }
+}
+
+/// Emit the key listeners of the widget, the keys the focused widget doesn't handle
+/// reach the widgets it is in. The key is still handled by the widget after it is emitted.
+pub(crate) fn connect_key_events<MSG, DSP>(
+    program: &DSP,
+    widget: &gtk::Widget,
+    attrs: &Vec<Attribute<MSG>>,
+) where
+    MSG: Debug + 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let on_key_down = find_callback(AttribKey::KeyDownEvent, attrs).cloned();
+    let on_key_up = find_callback(AttribKey::KeyUpEvent, attrs).cloned();
+    if on_key_down.is_none() && on_key_up.is_none() {
+        return;
+    }
+    // gdk doesn't tell whether a key press is repeated,
+    // it is when the key is pressed again before it is released
+    let pressed_key: Rc<Cell<Option<u32>>> = Rc::new(Cell::new(None));
+    if let Some(cb) = on_key_down {
+        let program_clone = program.clone();
+        let pressed_key = pressed_key.clone();
+        widget.connect_key_press_event(move |_widget, event| {
+            let keyval = event.get_keyval();
+            let repeat = pressed_key.replace(Some(keyval)) == Some(keyval);
+            let msg = cb.emit(gdk_key_event(event, repeat));
+            program_clone.dispatch(msg);
+            Inhibit(false)
+        });
+    }
+    let program_clone = program.clone();
+    widget.connect_key_release_event(move |_widget, event| {
+        pressed_key.set(None);
+        if let Some(cb) = &on_key_up {
+            let msg = cb.emit(gdk_key_event(event, false));
+            program_clone.dispatch(msg);
+        }
+        Inhibit(false)
+    });
+}
+
+fn gdk_key_event(event: &gdk::EventKey, repeat: bool) -> sauron_vdom::Event {
+    let modifiers = gdk_modifiers(event.get_state());
+    key::key_event(
+        gdk_key_name(event.get_keyval()),
+        gdk_key_code(event.get_hardware_keycode()),
+        modifiers,
+        repeat,
+    )
+}
+
+fn gdk_modifiers(state: ModifierType) -> Modifiers {
+    Modifiers {
+        ctrl: state.contains(ModifierType::CONTROL_MASK),
+        shift: state.contains(ModifierType::SHIFT_MASK),
+        alt: state.contains(ModifierType::MOD1_MASK),
+        meta: state.intersects(ModifierType::SUPER_MASK | ModifierType::META_MASK),
+    }
+}
+
+/// the web name of a gdk key, the keys which don't type a character are named
+/// from their gdk name, which is kept when there is no web name for it
+fn gdk_key_name(keyval: u32) -> String {
+    let name = gdk::keyval_name(keyval)
+        .map(|name| name.to_string())
+        .unwrap_or_default();
+    let web_name = match &*name {
+        "Return" | "KP_Enter" => "Enter",
+        "Escape" => "Escape",
+        "Tab" | "ISO_Left_Tab" => "Tab",
+        "BackSpace" => "Backspace",
+        "Delete" | "KP_Delete" => "Delete",
+        "Insert" | "KP_Insert" => "Insert",
+        "Home" | "KP_Home" => "Home",
+        "End" | "KP_End" => "End",
+        "Page_Up" | "KP_Page_Up" => "PageUp",
+        "Page_Down" | "KP_Page_Down" => "PageDown",
+        "Up" | "KP_Up" => "ArrowUp",
+        "Down" | "KP_Down" => "ArrowDown",
+        "Left" | "KP_Left" => "ArrowLeft",
+        "Right" | "KP_Right" => "ArrowRight",
+        "Control_L" | "Control_R" => "Control",
+        "Shift_L" | "Shift_R" => "Shift",
+        "Alt_L" | "Alt_R" => "Alt",
+        "Super_L" | "Super_R" | "Meta_L" | "Meta_R" => "Meta",
+        "Caps_Lock" => "CapsLock",
+        _ => {
+            return match gdk::keyval_to_unicode(keyval) {
+                Some(ch) if !ch.is_control() => ch.to_string(),
+                _ => name,
+            }
+        }
+    };
+    web_name.to_string()
+}
+
+/// The web name of the physical key of a gdk key event. The hardware keycode of X11 and
+/// wayland is the linux evdev code plus 8, the keys of other systems are unidentified.
+fn gdk_key_code(hardware_keycode: u16) -> String {
+    let letter = |row: &str, index: u16| format!("Key{}", &row[index as usize..][..1]);
+    let code = match hardware_keycode.saturating_sub(8) {
+        1 => "Escape",
+        evdev @ 2..=11 => return format!("Digit{}", (evdev - 1) % 10),
+        12 => "Minus",
+        13 => "Equal",
+        14 => "Backspace",
+        15 => "Tab",
+        evdev @ 16..=25 => return letter("QWERTYUIOP", evdev - 16),
+        26 => "BracketLeft",
+        27 => "BracketRight",
+        28 => "Enter",
+        29 => "ControlLeft",
+        evdev @ 30..=38 => return letter("ASDFGHJKL", evdev - 30),
+        39 => "Semicolon",
+        40 => "Quote",
+        41 => "Backquote",
+        42 => "ShiftLeft",
+        43 => "Backslash",
+        evdev @ 44..=50 => return letter("ZXCVBNM", evdev - 44),
+        51 => "Comma",
+        52 => "Period",
+        53 => "Slash",
+        54 => "ShiftRight",
+        55 => "NumpadMultiply",
+        56 => "AltLeft",
+        57 => "Space",
+        58 => "CapsLock",
+        evdev @ 59..=68 => return format!("F{}", evdev - 58),
+        69 => "NumLock",
+        70 => "ScrollLock",
+        71 => "Numpad7",
+        72 => "Numpad8",
+        73 => "Numpad9",
+        74 => "NumpadSubtract",
+        75 => "Numpad4",
+        76 => "Numpad5",
+        77 => "Numpad6",
+        78 => "NumpadAdd",
+        79 => "Numpad1",
+        80 => "Numpad2",
+        81 => "Numpad3",
+        82 => "Numpad0",
+        83 => "NumpadDecimal",
+        86 => "IntlBackslash",
+        87 => "F11",
+        88 => "F12",
+        96 => "NumpadEnter",
+        97 => "ControlRight",
+        98 => "NumpadDivide",
+        99 => "PrintScreen",
+        100 => "AltRight",
+        102 => "Home",
+        103 => "ArrowUp",
+        104 => "PageUp",
+        105 => "ArrowLeft",
+        106 => "ArrowRight",
+        107 => "End",
+        108 => "ArrowDown",
+        109 => "PageDown",
+        110 => "Insert",
+        111 => "Delete",
+        119 => "Pause",
+        125 => "MetaLeft",
+        126 => "MetaRight",
+        127 => "ContextMenu",
+        _ => "Unidentified",
+    };
+    code.to_string()
+}
+
+/// Emit the mouse listeners of the widget. The events over a child reach the widgets
+/// it is in, widgets that don't have a window of their own such as boxes and labels
+/// only get the events over their children.
+pub(crate) fn connect_mouse_events<MSG, DSP>(
+    program: &DSP,
+    widget: &gtk::Widget,
+    attrs: &Vec<Attribute<MSG>>,
+) where
+    MSG: Debug + 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    // the text view of a textarea handles the presses, they don't reach the scrolled window
+    let widget: &gtk::Widget = &match text_view_of(widget) {
+        Some(text_view) => text_view.upcast(),
+        None => widget.clone(),
+    };
+    let on_mouse_down = find_callback(AttribKey::MouseDownEvent, attrs).cloned();
+    let on_double_click = find_callback(AttribKey::DoubleClickEvent, attrs).cloned();
+    if on_mouse_down.is_some() || on_double_click.is_some() {
+        widget.add_events(EventMask::BUTTON_PRESS_MASK.bits() as i32);
+        let program_clone = program.clone();
+        widget.connect_button_press_event(move |widget, event| {
+            // the second press of a double click is followed by a double button press
+            let (r#type, cb) = if event.get_event_type() == EventType::DoubleButtonPress {
+                ("dblclick", &on_double_click)
+            } else {
+                ("mousedown", &on_mouse_down)
+            };
+            if let Some(cb) = cb {
+                let msg = cb.emit(gdk_button_event(widget, r#type, event));
+                program_clone.dispatch(msg);
+            }
+            Inhibit(false)
+        });
+    }
+    if let Some(cb) = find_callback(AttribKey::MouseUpEvent, attrs) {
+        widget.add_events(EventMask::BUTTON_RELEASE_MASK.bits() as i32);
+        let cb_clone = cb.clone();
+        let program_clone = program.clone();
+        widget.connect_button_release_event(move |widget, event| {
+            let msg = cb_clone.emit(gdk_button_event(widget, "mouseup", event));
+            program_clone.dispatch(msg);
+            Inhibit(false)
+        });
+    }
+    if let Some(cb) = find_callback(AttribKey::MouseMoveEvent, attrs) {
+        widget.add_events(EventMask::POINTER_MOTION_MASK.bits() as i32);
+        let cb_clone = cb.clone();
+        let program_clone = program.clone();
+        widget.connect_motion_notify_event(move |widget, event| {
+            let state = event.get_state();
+            let button = if state.contains(ModifierType::BUTTON3_MASK) {
+                MouseButton::Right
+            } else if state.contains(ModifierType::BUTTON2_MASK) {
+                MouseButton::Middle
+            } else {
+                MouseButton::Left
+            };
+            let (widget_position, window_position) = pointer_positions(widget, event.get_root());
+            let mouse_event = mouse::mouse_event(
+                "mousemove",
+                widget_position,
+                window_position,
+                button,
+                gdk_modifiers(state),
+                0,
+            );
+            let msg = cb_clone.emit(mouse_event);
+            program_clone.dispatch(msg);
+            Inhibit(false)
+        });
+    }
+}
+
+/// The mouse event of a gdk button press or release over the widget. Gdk follows the press
+/// which completes a double or a triple click with a double or a triple button press,
+/// the other presses and the releases are counted as one.
+fn gdk_button_event(
+    widget: &gtk::Widget,
+    r#type: &'static str,
+    event: &gdk::EventButton,
+) -> sauron_vdom::Event {
+    let button = match event.get_button() {
+        2 => MouseButton::Middle,
+        3 => MouseButton::Right,
+        _ => MouseButton::Left,
+    };
+    let click_count = match event.get_event_type() {
+        EventType::DoubleButtonPress => 2,
+        EventType::TripleButtonPress => 3,
+        _ => 1,
+    };
+    let (widget_position, window_position) = pointer_positions(widget, event.get_root());
+    mouse::mouse_event(
+        r#type,
+        widget_position,
+        window_position,
+        button,
+        gdk_modifiers(event.get_state()),
+        click_count,
+    )
+}
+
+/// The mouse event of a click, from the button release being handled.
+/// A widget clicked with the keyboard gets an event without a position.
+fn click_event(widget: &gtk::Widget) -> sauron_vdom::Event {
+    gtk::get_current_event()
+        .map(|event| event.downcast::<gdk::EventButton>().ok())
+        .flatten()
+        .map(|event| gdk_button_event(widget, "click", &event))
+        .unwrap_or_else(|| sauron_vdom::Event::MouseEvent(MouseEvent::default()))
+}
+
+/// the position of the pointer in the widget and in its window,
+/// from the position of the pointer on the screen
+fn pointer_positions(widget: &gtk::Widget, root: (f64, f64)) -> ((i32, i32), (i32, i32)) {
+    let toplevel = widget.get_toplevel().unwrap_or_else(|| widget.clone());
+    let (_, origin_x, origin_y) = toplevel
+        .get_window()
+        .map(|window| window.get_origin())
+        .unwrap_or((0, 0, 0));
+    let window_position = (root.0 as i32 - origin_x, root.1 as i32 - origin_y);
+    let (widget_x, widget_y) = widget
+        .translate_coordinates(&toplevel, 0, 0)
+        .unwrap_or((0, 0));
+    (
+        (window_position.0 - widget_x, window_position.1 - widget_y),
+        window_position,
+    )
+}
+
+/// Emit the focus and blur listeners of the widget when it takes or loses the focus.
+/// The text view of a textarea is the one taking the focus, not the scrolled window.
+pub(crate) fn connect_focus_events<MSG, DSP>(
+    program: &DSP,
+    widget: &gtk::Widget,
+    attrs: &Vec<Attribute<MSG>>,
+) where
+    MSG: Debug + 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let widget: &gtk::Widget = &match text_view_of(widget) {
+        Some(text_view) => text_view.upcast(),
+        None => widget.clone(),
+    };
+    if let Some(cb) = find_callback(AttribKey::FocusEvent, attrs) {
+        let cb_clone = cb.clone();
+        let program_clone = program.clone();
+        widget.connect_focus_in_event(move |widget, _event| {
+            let msg = cb_clone.emit(InputEvent::new(focus_value(widget)));
+            program_clone.dispatch(msg);
+            Inhibit(false)
+        });
+    }
+    if let Some(cb) = find_callback(AttribKey::BlurEvent, attrs) {
+        let cb_clone = cb.clone();
+        let program_clone = program.clone();
+        widget.connect_focus_out_event(move |widget, _event| {
+            let msg = cb_clone.emit(InputEvent::new(focus_value(widget)));
+            program_clone.dispatch(msg);
+            Inhibit(false)
+        });
+    }
+}
+
+/// the value given to the focus and blur listeners
+fn focus_value(widget: &gtk::Widget) -> String {
+    if let Some(entry) = widget.downcast_ref::<Entry>() {
+        entry.get_buffer().get_text()
+    } else if let Some(text_view) = widget.downcast_ref::<TextView>() {
+        text_view
+            .get_buffer()
+            .map(|buffer| {
+                buffer.get_text(&buffer.get_start_iter(), &buffer.get_end_iter(), true)
+            })
+            .flatten()
+            .map(|text| text.to_string())
+            .unwrap_or_default()
+    } else if let Some(check_button) = widget.downcast_ref::<CheckButton>() {
+        // radio buttons are check buttons too
+        check_button.get_active().to_string()
+    } else {
+        String::new()
+    }
+}
+
+/// Build or patch the widgets of a view. The change signals emitted meanwhile are not
+/// from the user, such as a radio unchecked when another one of its group is checked
+/// by the view or a switch set to the value of the new view, so the listeners are not
+/// emitted for them, see is_applying_view.
+fn applying_view<R>(apply: impl FnOnce() -> R) -> R {
+    let was_applying = APPLYING_VIEW.with(|applying| applying.replace(true));
+    let result = apply();
+    APPLYING_VIEW.with(|applying| applying.set(was_applying));
+    result
+}
+
+/// whether a view is being built or patched, the signal handlers which dispatch a message
+/// return early when it is: the view is set by the app, and dispatching would update the app
+/// while it is still borrowed for the view being applied
+fn is_applying_view() -> bool {
+    APPLYING_VIEW.with(|applying| applying.get())
+}
+
+/// emit the new state of a checkbox or a radio to its toggle listener
+fn connect_toggled<MSG, DSP>(
+    program: &DSP,
+    check_button: &CheckButton,
+    attrs: &Vec<Attribute<MSG>>,
+) where
+    MSG: Debug + 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    if let Some(cb) = find_callback(AttribKey::ToggleEvent, attrs) {
+        let cb_clone = cb.clone();
+        let program_clone = program.clone();
+        check_button.connect_toggled(move |check_button| {
+            if is_applying_view() {
+                return;
+            }
+            let input_event = InputEvent::new(check_button.get_active().to_string());
+            let msg = cb_clone.emit(input_event);
+            program_clone.dispatch(msg);
+        });
+    }
+}
+
+/// a read only checkbox or radio ignores the clicks and the keys which would toggle it
+fn ignore_when_read_only(check_button: &CheckButton) {
+    fn is_read_only(widget: &gtk::Widget) -> Inhibit {
+        Inhibit(widget.get_style_context().has_class(READ_ONLY_CLASS))
+    }
+    check_button.connect_button_press_event(|widget, _event| is_read_only(widget.upcast_ref()));
+    check_button.connect_key_press_event(|widget, _event| is_read_only(widget.upcast_ref()));
+}
+
+/// the text view inside a scrolled window takes the focus instead of the scrolled window
+pub(crate) fn grab_focus(widget: &gtk::Widget) {
+    match widget
+        .downcast_ref::<ScrolledWindow>()
+        .map(|scroll_view| scroll_view.get_child())
+        .flatten()
+    {
+        Some(child) => child.grab_focus(),
+        None => widget.grab_focus(),
+    }
+}
+
+/// gtk has no tab index, instead the focus chain of the container is set so the children
+/// with a tab index take the focus first in the order of their tab index
+fn set_tab_order(container: &Container, mut children: Vec<(Option<u32>, gtk::Widget)>) {
+    // the children of an expander are in the box inside it
+    let container = container
+        .downcast_ref::<Expander>()
+        .map(|expander| expander.get_child())
+        .flatten()
+        .map(|content| content.downcast::<Container>().ok())
+        .flatten()
+        .unwrap_or_else(|| container.clone());
+    children.sort_by_key(|(tab_index, _)| tab_index.unwrap_or(u32::max_value()));
+    let focus_chain: Vec<gtk::Widget> = children.into_iter().map(|(_, widget)| widget).collect();
+    container.set_focus_chain(&focus_chain);
+}
+
+/// the icon of a tool item is an icon name from the icon theme or an image file
+pub(crate) fn set_tool_icon<T>(tool_button: &T, icon: &str)
+where
+    T: IsA<ToolButton>,
+{
+    if Path::new(icon).is_file() {
+        tool_button.set_icon_widget(Some(&Image::new_from_file(icon)));
+    } else {
+        tool_button.set_icon_name(Some(icon));
+    }
+}
+
+/// add the widget to a container which needs the child attributes to place it,
+/// index is the position of the child among its siblings
+pub(crate) fn add_to_container<MSG>(
+    container: &Container,
+    index: usize,
+    widget: &gtk::Widget,
+    attrs: &Vec<Attribute<MSG>>,
+) where
+    MSG: 'static,
+{
+    // the radios in one container form a group, gtk only unchecks a radio when another
+    // radio of its group is checked. Joining the group unchecks the radio joining it
+    if let Some(radio) = widget.downcast_ref::<RadioButton>() {
+        let sibling = container
+            .get_children()
+            .into_iter()
+            .find_map(|child| child.downcast::<RadioButton>().ok());
+        if let Some(sibling) = sibling {
+            radio.join_group(Some(&sibling));
+            radio.set_active(state::is_checked(attrs));
+        }
+    }
+    let flex = Flex::from_attrs(attrs);
+    if let Some(grid) = container.downcast_ref::<Grid>() {
+        if flex.expands() {
+            widget.set_hexpand(true);
+            widget.set_vexpand(true);
+        }
+        let cell = GridCell::from_attrs(attrs);
+        grid.attach(
+            widget,
+            cell.column as i32,
+            cell.row as i32,
+            cell.column_span as i32,
+            cell.row_span as i32,
+        );
+        set_grid_child_index(widget, index);
+    } else if let Some(toolbar) = container.downcast_ref::<Toolbar>() {
+        // widgets other than the tool items are wrapped in a tool item
+        let item = match widget.downcast_ref::<ToolItem>() {
+            Some(item) => item.clone(),
+            None => {
+                let item = ToolItem::new();
+                item.add(widget);
+                item.set_expand(is_flexible_space(widget) || flex.expands());
+                item.show();
+                item
+            }
+        };
+        toolbar.insert(&item, index as i32);
+    } else if let Some(overlay) = container.downcast_ref::<Overlay>() {
+        widget.set_halign(gtk_align(Align::find(AttribKey::HorizontalAlign, attrs)));
+        widget.set_valign(gtk_align(Align::find(AttribKey::VerticalAlign, attrs)));
+        if overlay.get_child().is_none() {
+            overlay.add(widget);
+        } else {
+            overlay.add_overlay(widget);
+        }
+    } else if let Some(stack) = container.downcast_ref::<Stack>() {
+        // a stack can only show children that are visible
+        widget.show();
+        stack.add_named(widget, &layout::page_name(index, attrs));
+    } else if let Some(gbox) = container.downcast_ref::<gtk::Box>() {
+        // a flexible space takes the space left along the orientation of the box,
+        // gtk has no grow factors so every growing child takes the same share
+        let expand = is_flexible_space(widget) || flex.expands();
+        gbox.pack_start(widget, expand, flex.fill, 0);
+        if let Some(align) = cross_alignment(gbox) {
+            align_across(gbox, widget, align);
+        }
+    } else {
+        container.add(widget);
+    }
+}
+
+/// Remember the index of the node of a child of a grid, until the child is destroyed
+fn set_grid_child_index(widget: &gtk::Widget, index: usize) {
+    let previous =
+        GRID_CHILD_INDEX.with(|indexes| indexes.borrow_mut().insert(widget.clone(), index));
+    if previous.is_none() {
+        widget.connect_destroy(|widget| {
+            GRID_CHILD_INDEX.with(|indexes| indexes.borrow_mut().remove(widget));
+        });
+    }
+}
+
+/// the children of a grid in the order of their nodes
+pub(crate) fn grid_children(grid: &Grid) -> Vec<gtk::Widget> {
+    let mut children = grid.get_children();
+    GRID_CHILD_INDEX.with(|indexes| {
+        let indexes = indexes.borrow();
+        children.sort_by_key(|child| indexes.get(child).copied().unwrap_or(usize::MAX));
+    });
+    children
+}
+
+/// Move a child of a grid to the cell of its patched grid attributes,
+/// the parts of the cell that are not patched stay the same
+pub(crate) fn set_grid_cell<MSG>(widget: &gtk::Widget, attrs: &Vec<Attribute<MSG>>)
+where
+    MSG: 'static,
+{
+    let grid = match widget
+        .get_parent()
+        .map(|parent| parent.downcast::<Grid>().ok())
+        .flatten()
+    {
+        Some(grid) => grid,
+        None => return,
+    };
+    let cell = GridCell::from_attrs(attrs);
+    let cell_properties = [
+        (AttribKey::GridRow, "top-attach", cell.row),
+        (AttribKey::GridColumn, "left-attach", cell.column),
+        (AttribKey::RowSpan, "height", cell.row_span),
+        (AttribKey::ColumnSpan, "width", cell.column_span),
+    ];
+    for (key, property, value) in cell_properties.iter() {
+        if attrs.iter().any(|att| att.name == *key) {
+            grid.child_set_property(widget, property, &Value::from(&(*value as i32)));
+        }
+    }
+}
+
+/// Align a child of a stack to its patched halign and valign attributes
+pub(crate) fn set_layer_alignment<MSG>(widget: &gtk::Widget, attrs: &Vec<Attribute<MSG>>)
+where
+    MSG: 'static,
+{
+    let in_overlay = widget
+        .get_parent()
+        .map_or(false, |parent| parent.downcast::<Overlay>().is_ok());
+    if !in_overlay {
+        return;
+    }
+    let patched = |key: AttribKey| attrs.iter().any(|att| att.name == key);
+    if patched(AttribKey::HorizontalAlign) {
+        widget.set_halign(gtk_align(Align::find(AttribKey::HorizontalAlign, attrs)));
+    }
+    if patched(AttribKey::VerticalAlign) {
+        widget.set_valign(gtk_align(Align::find(AttribKey::VerticalAlign, attrs)));
+    }
+}
+
+/// the text of the date picker button
+pub(crate) fn date_label(date: Option<Date>) -> String {
+    date.map(|date| date.to_string())
+        .unwrap_or_else(|| "YYYY-MM-DD".to_string())
+}
+
+/// the months of the calendar start at 0
+pub(crate) fn select_date(calendar: &Calendar, date: Date) {
+    // avoid re-emitting the change event when the date didn't change
+    if calendar_date(calendar) != date {
+        calendar.select_month(date.month - 1, date.year as u32);
+        calendar.select_day(date.day);
+    }
+}
+
+fn calendar_date(calendar: &Calendar) -> Date {
+    let (year, month, day) = calendar.get_date();
+    Date {
+        year: year as i32,
+        month: month + 1,
+        day,
+    }
+}
+
+/// a wrapping spin button showing its value with 2 digits
+fn time_spin_button(max: f64, value: u32) -> SpinButton {
+    let spin_button = SpinButton::new_with_range(0.0, max, 1.0);
+    spin_button.set_wrap(true);
+    spin_button.set_value(value as f64);
+    spin_button.connect_output(|spin_button| {
+        spin_button.set_text(&format!("{:02}", spin_button.get_value_as_int()));
+        Inhibit(true)
+    });
+    spin_button
+}
+
+/// the minimum size is the size request of the widget,
+/// gtk has no maximum size so it is not applied
+pub(crate) fn set_size_constraints<MSG>(widget: &gtk::Widget, attrs: &Vec<Attribute<MSG>>)
+where
+    MSG: 'static,
+{
+    let constraints = SizeConstraints::from_attrs(attrs);
+    if constraints.min_width.is_some() || constraints.min_height.is_some() {
+        let (width, height) = widget.get_size_request();
+        widget.set_size_request(
+            constraints.min_width.map(|w| w as i32).unwrap_or(width),
+            constraints.min_height.map(|h| h as i32).unwrap_or(height),
+        );
+    }
+}
+
+/// Change how the widget is packed in its parent box when its flex attributes changed,
+/// only the attributes present are changed
+pub(crate) fn set_flex_attributes<MSG>(widget: &gtk::Widget, attrs: &Vec<Attribute<MSG>>)
+where
+    MSG: 'static,
+{
+    if !Flex::is_declared(attrs) {
+        return;
+    }
+    let gbox = match widget
+        .get_parent()
+        .map(|parent| parent.downcast::<gtk::Box>().ok())
+        .flatten()
+    {
+        Some(gbox) => gbox,
+        None => return,
+    };
+    let (mut expand, mut fill, padding, pack_type) = gbox.query_child_packing(widget);
+    for att in attrs {
+        if let Some(value) = att.get_value() {
+            match att.name {
+                AttribKey::Expand => expand = value.as_bool().unwrap_or(false),
+                AttribKey::FlexGrow => expand = value.as_f64().unwrap_or(0.0) > 0.0,
+                AttribKey::Fill => fill = value.as_bool().unwrap_or(true),
+                _ => (),
+            }
+        }
+    }
+    gbox.set_child_packing(widget, expand, fill, padding, pack_type);
+}
+
+/// apply the alignment, spacing, padding and margin attributes of a row or a column,
+/// only the attributes present are changed
+pub(crate) fn set_box_layout<MSG>(gbox: &gtk::Box, attrs: &Vec<Attribute<MSG>>)
+where
+    MSG: 'static,
+{
+    for att in attrs {
+        if let Some(value) = att.get_value() {
+            match att.name {
+                AttribKey::Spacing => {
+                    gbox.set_spacing(value.as_f64().unwrap_or(0.0).max(0.0) as i32)
+                }
+                // gtk has no alignment of the children along a box, instead the box is shrunk
+                // to its children and aligned inside the space it is given
+                AttribKey::Alignment => {
+                    let align = match Alignment::from_value(value).unwrap_or(Alignment::Start) {
+                        Alignment::Start => gtk::Align::Fill,
+                        Alignment::Center => gtk::Align::Center,
+                        Alignment::End => gtk::Align::End,
+                    };
+                    match gbox.get_orientation() {
+                        Orientation::Horizontal => gbox.set_halign(align),
+                        _ => gbox.set_valign(align),
+                    }
+                }
+                AttribKey::CrossAlignment => {
+                    let align = Align::from_value(value).unwrap_or(Align::Fill);
+                    set_cross_alignment(gbox, align);
+                }
+                AttribKey::Margin => {
+                    let margin = Insets::from_value(value).unwrap_or_default();
+                    gbox.set_margin_top(margin.top as i32);
+                    gbox.set_margin_end(margin.end as i32);
+                    gbox.set_margin_bottom(margin.bottom as i32);
+                    gbox.set_margin_start(margin.start as i32);
+                }
+                AttribKey::Padding => {
+                    let padding = Insets::from_value(value).unwrap_or_default();
+                    set_widget_css(
+                        gbox.upcast_ref(),
+                        "padding",
+                        &format!(
+                            "padding: {}px {}px {}px {}px;",
+                            padding.top, padding.end, padding.bottom, padding.start
+                        ),
+                    );
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// The cross alignment of a box is kept in a style class of the box,
+/// so the children added later by the patches are aligned like the others
+fn set_cross_alignment(gbox: &gtk::Box, align: Align) {
+    let style = gbox.get_style_context();
+    for other in &[Align::Fill, Align::Start, Align::Center, Align::End] {
+        style.remove_class(&format!("cross-{}", other.as_str()));
+    }
+    style.add_class(&format!("cross-{}", align.as_str()));
+    for child in gbox.get_children() {
+        align_across(gbox, &child, align);
+    }
+}
+
+fn cross_alignment(gbox: &gtk::Box) -> Option<Align> {
+    let style = gbox.get_style_context();
+    [Align::Fill, Align::Start, Align::Center, Align::End]
+        .iter()
+        .find(|align| style.has_class(&format!("cross-{}", align.as_str())))
+        .cloned()
+}
+
+fn align_across(gbox: &gtk::Box, child: &gtk::Widget, align: Align) {
+    match gbox.get_orientation() {
+        Orientation::Horizontal => child.set_valign(gtk_align(align)),
+        _ => child.set_halign(gtk_align(align)),
+    }
+}
+
+/// Replace the css declarations the widget has for the concern,
+/// the declarations only apply to the widget and not to its children
+pub(crate) fn set_widget_css(widget: &gtk::Widget, concern: &'static str, declarations: &str) {
+    let provider = WIDGET_CSS.with(|widget_css| {
+        widget_css
+            .borrow_mut()
+            .entry((widget.clone(), concern))
+            .or_insert_with(|| {
+                let provider = CssProvider::new();
+                widget
+                    .get_style_context()
+                    .add_provider(&provider, gtk::STYLE_PROVIDER_PRIORITY_APPLICATION as u32);
+                widget.connect_destroy(move |widget| {
+                    WIDGET_CSS.with(|widget_css| {
+                        widget_css.borrow_mut().remove(&(widget.clone(), concern));
+                    });
+                });
+                provider
+            })
+            .clone()
+    });
+    if let Err(e) = provider.load_from_data(format!("* {{ {} }}", declarations).as_bytes()) {
+        error!("unable to apply the style {}: {}", declarations, e);
+    }
+}
+
+/// apply the style attribute of the widget, it takes precedence over the theme
+pub(crate) fn set_style<MSG>(widget: &gtk::Widget, attrs: &Vec<Attribute<MSG>>)
+where
+    MSG: 'static,
+{
+    if let Some(style) = Style::find(attrs) {
+        set_widget_css(widget, "style", &style_css(&style));
+    }
+}
+
+/// the themes of gtk draw the background of buttons with a gradient image,
+/// which would hide the background color
+fn style_css(style: &Style) -> String {
+    if style.background.is_some() {
+        format!("{} background-image: none;", style.to_css())
+    } else {
+        style.to_css()
+    }
+}
+
+/// gtk has no system wide color scheme, the dark variant is asked for with the
+/// gtk-application-prefer-dark-theme setting or by choosing a dark theme such as Adwaita-dark
+fn system_color_scheme() -> ColorScheme {
+    let prefers_dark = Settings::get_default()
+        .map(|settings| {
+            settings.get_property_gtk_application_prefer_dark_theme()
+                || settings
+                    .get_property_gtk_theme_name()
+                    .map(|name| name.to_lowercase().ends_with("-dark"))
+                    .unwrap_or(false)
+        })
+        .unwrap_or(false);
+    if prefers_dark {
+        ColorScheme::Dark
+    } else {
+        ColorScheme::Light
+    }
+}
+
+/// apply the theme of the window for the current color scheme, if it has one
+fn apply_theme<MSG>(window: &gtk::Window, attrs: &Vec<Attribute<MSG>>)
+where
+    MSG: 'static,
+{
+    if let Some(theme) = Theme::for_color_scheme(attrs, system_color_scheme()) {
+        set_theme(window, &theme);
+    }
+}
+
+/// Replace the theme of the screen the window is on. The theme is below the style
+/// of the widgets, so the style attribute of a widget takes precedence over it.
+fn set_theme(window: &gtk::Window, theme: &Theme) {
+    let screen = match WidgetExt::get_screen(window) {
+        Some(screen) => screen,
+        None => return,
+    };
+    // the link button is a button too, it only takes the color of the accent
+    let rules = vec![
+        ("window", theme.style_for(&Widget::Window)),
+        ("button:not(.link)", theme.style_for(&Widget::Button)),
+        ("button.link", theme.style_for(&Widget::Link)),
+        (
+            "entry, textview, spinbutton",
+            theme.style_for(&Widget::TextInput),
+        ),
+    ];
+    let mut css: Vec<String> = rules
+        .into_iter()
+        .filter(|(_, style)| !style.is_empty())
+        .map(|(selector, style)| format!("{} {{ {} }}", selector, style_css(&style)))
+        .collect();
+    if let Some(accent) = theme.accent {
+        css.push(format!("*:selected {{ background-color: {}; }}", accent));
+    }
+    let css = css.join("\n");
+    THEME_CSS.with(|theme_css| {
+        let mut theme_css = theme_css.borrow_mut();
+        let (provider, current_css) = theme_css.get_or_insert_with(|| {
+            let provider = CssProvider::new();
+            StyleContext::add_provider_for_screen(
+                &screen,
+                &provider,
+                gtk::STYLE_PROVIDER_PRIORITY_APPLICATION as u32 - 1,
+            );
+            (provider, String::new())
+        });
+        // reloading the css restyles every widget, it is only done when the theme changed
+        if *current_css != css {
+            if let Err(e) = provider.load_from_data(css.as_bytes()) {
+                error!("unable to apply the theme {:?}: {}", theme, e);
+            }
+            *current_css = css;
+        }
+    });
+}
+
+fn is_flexible_space(widget: &gtk::Widget) -> bool {
+    widget.get_style_context().has_class(SPACE_CLASS)
+}
+
+pub(crate) fn gtk_orientation(orientation: layout::Orientation) -> Orientation {
+    match orientation {
+        layout::Orientation::Horizontal => Orientation::Horizontal,
+        layout::Orientation::Vertical => Orientation::Vertical,
+    }
+}
+
+fn gtk_align(align: Align) -> gtk::Align {
+    match align {
+        Align::Fill => gtk::Align::Fill,
+        Align::Start => gtk::Align::Start,
+        Align::Center => gtk::Align::Center,
+        Align::End => gtk::Align::End,
+    }
+}
+
+pub(crate) fn stack_transition_type(transition: Transition) -> StackTransitionType {
+    match transition {
+        Transition::None => StackTransitionType::None,
+        Transition::Crossfade => StackTransitionType::Crossfade,
+        Transition::SlideLeft => StackTransitionType::SlideLeft,
+        Transition::SlideRight => StackTransitionType::SlideRight,
+        Transition::SlideUp => StackTransitionType::SlideUp,
+        Transition::SlideDown => StackTransitionType::SlideDown,
+    }
+}
+
+/// the image of an image or svg, from the source attribute or else the data attribute
+pub(crate) fn image_animation<MSG>(
+    widget: &Widget,
+    attrs: &Vec<Attribute<MSG>>,
+) -> Option<PixbufAnimation>
+where
+    MSG: 'static,
+{
+    if let Some(source) = find_value(AttribKey::Source, attrs) {
+        return source_animation(&source.to_string());
+    }
+    let bytes = find_value(AttribKey::Data, attrs)
+        .map(|v| v.as_bytes())
+        .flatten()?;
+    match widget {
+        Widget::Svg => load_animation(bytes, "image/svg+xml"),
+        _ => data_animation(bytes),
+    }
+}
+
+/// load the image file of the source, each file is only loaded once
+fn source_animation(source: &str) -> Option<PixbufAnimation> {
+    let path = util::source_path(source);
+    if let Some(animation) = ANIMATION_CACHE.with(|cache| cache.borrow().get(&path).cloned()) {
+        return Some(animation);
+    }
+    let animation = match PixbufAnimation::new_from_file(&path) {
+        Ok(animation) => Some(animation),
+        // gdk-pixbuf has no loader for the format, try converting it
+        Err(_) => match fs::read(&path) {
+            Ok(bytes) => data_animation(&bytes),
+            Err(e) => {
+                error!("unable to read image {}: {}", path.display(), e);
+                None
+            }
+        },
+    };
+    if let Some(animation) = &animation {
+        ANIMATION_CACHE.with(|cache| cache.borrow_mut().insert(path, animation.clone()));
+    }
+    animation
+}
+
+/// load image data in any format the image crate can decode,
+/// the formats gdk-pixbuf has no loader for are converted to PNG
+fn data_animation(bytes: &[u8]) -> Option<PixbufAnimation> {
+    let (bytes, mime) = util::displayable_image(bytes, PIXBUF_FORMATS)?;
+    load_animation(&bytes, mime)
+}
+
+fn load_animation(bytes: &[u8], mime: &str) -> Option<PixbufAnimation> {
+    let pixbuf_loader = match PixbufLoader::new_with_mime_type(mime) {
+        Ok(pixbuf_loader) => pixbuf_loader,
+        Err(e) => {
+            error!("no image loader for {}: {}", mime, e);
+            return None;
+        }
+    };
+    if let Err(e) = pixbuf_loader
+        .write(bytes)
+        .and_then(|_| pixbuf_loader.close())
+    {
+        error!("unable to load {} image: {}", mime, e);
+        return None;
+    }
+    pixbuf_loader.get_animation()
+}
+
+/// The image is shown in a scroll view which has the size of the width and height attributes,
+/// or else the size allocated by the parent, and the pixbuf is scaled to it with the fit mode.
+fn fitted_image<MSG>(
+    animation: Option<PixbufAnimation>,
+    attrs: &Vec<Attribute<MSG>>,
+) -> ScrolledWindow
+where
+    MSG: 'static,
+{
+    let fit = Fit::find(attrs);
+    let width = find_value(AttribKey::Width, attrs)
+        .map(|v| v.as_f64())
+        .flatten();
+    let height = find_value(AttribKey::Height, attrs)
+        .map(|v| v.as_f64())
+        .flatten();
+    let scroll_view = ScrolledWindow::new(None::<&Adjustment>, None::<&Adjustment>);
+    // the pixbuf is scaled to the scroll view, so it never scrolls
+    scroll_view.set_policy(PolicyType::External, PolicyType::External);
+    if width.is_some() || height.is_some() {
+        let (box_width, box_height) = util::box_size(
+            width,
+            height,
+            animation
+                .as_ref()
+                .map(|animation| (animation.get_width() as u32, animation.get_height() as u32))
+                .unwrap_or((1, 1)),
+        );
+        scroll_view.set_size_request(box_width as i32, box_height as i32);
+    } else {
+        scroll_view.set_propagate_natural_width(true);
+        scroll_view.set_propagate_natural_height(true);
+    }
+    // the fit mode is kept with the widget, so the image can be refitted when the data changes
+    scroll_view
+        .get_style_context()
+        .add_class(&format!("fit-{}", fit.as_str()));
+    set_fitted_image(&scroll_view, animation);
+    scroll_view
+}
+
+/// replace the image in the scroll view created by `fitted_image`,
+/// an animated image is played at its own size since only still images can be scaled
+pub(crate) fn set_fitted_image(scroll_view: &ScrolledWindow, animation: Option<PixbufAnimation>) {
+    let style = scroll_view.get_style_context();
+    let fit = [Fit::Contain, Fit::Cover, Fit::Fill, Fit::None]
+        .iter()
+        .find(|fit| style.has_class(&format!("fit-{}", fit.as_str())))
+        .cloned()
+        .unwrap_or(Fit::Contain);
+    for child in scroll_view.get_children() {
+        scroll_view.remove(&child);
+    }
+    let animation = match animation {
+        Some(animation) => animation,
+        None => return,
+    };
+    if !animation.is_static_image() {
+        scroll_view.add(&Image::new_from_animation(&animation));
+        scroll_view.show_all();
+        return;
+    }
+    let pixbuf = match animation.get_static_image() {
+        Some(pixbuf) => pixbuf,
+        None => return,
+    };
+    let image = Image::new_from_pixbuf(Some(&pixbuf));
+    // the image is wrapped in a viewport, which gets the allocated size of the scroll view
+    scroll_view.add(&image);
+    if let Some(viewport) = scroll_view.get_child() {
+        viewport.connect_size_allocate(move |_, allocation| {
+            let image_size = (pixbuf.get_width() as u32, pixbuf.get_height() as u32);
+            let box_size = (
+                allocation.width.max(1) as u32,
+                allocation.height.max(1) as u32,
+            );
+            let scaled_size = util::fit_size(fit, image_size, box_size);
+            let scaled = if scaled_size == image_size {
+                Some(pixbuf.clone())
+            } else {
+                pixbuf.scale_simple(
+                    scaled_size.0 as i32,
+                    scaled_size.1 as i32,
+                    InterpType::Bilinear,
+                )
+            };
+            let (x, y, width, height) = util::visible_region(scaled_size, box_size);
+            let visible = scaled.map(|scaled| {
+                scaled.new_subpixbuf(x as i32, y as i32, width as i32, height as i32)
+            });
+            let current_size = image
+                .get_pixbuf()
+                .map(|current| (current.get_width(), current.get_height()));
+            // only set when the size changed, since setting the pixbuf allocates again
+            if let Some(visible) = visible {
+                if current_size != Some((visible.get_width(), visible.get_height())) {
+                    image.set_from_pixbuf(Some(&visible));
+                }
+            }
+        });
+    }
+    scroll_view.show_all();
+}
@@ -1,19 +1,36 @@
 use super::{Dispatch, GtkBackend};
-use crate::{AttribKey, Attribute, Patch};
-use gdk_pixbuf::{PixbufLoader, PixbufLoaderExt};
-use gtk::{prelude::*, Button, Container, ContainerExt, Image, TextView, Widget, ScrolledWindow, Viewport};
+use crate::{
+    widget::{
+        attribute::find_value,
+        date::{Date, Time},
+        focus, state, Orientation, Transition,
+    },
+    AttribKey, Attribute, Node, Patch,
+};
+use gtk::{
+    prelude::*, Button, Calendar, CheckButton, Container, ContainerExt, Entry, EntryIconPosition,
+    EventBox, Expander, Grid, InputPurpose, Label, LinkButton, MenuButton, ScrolledWindow,
+    Separator, SpinButton, Stack, Statusbar, Switch, TextView, ToggleToolButton, ToolButton,
+    ToolItem, Toolbar, Widget,
+};
+use log::*;
 use std::{
     collections::{HashMap, HashSet},
     fmt::Debug,
     rc::Rc,
 };
 
-pub fn apply_patches<MSG, DSP>(program: &DSP, container: &Container, patches: &Vec<Patch<MSG>>)
-where
+/// Patch the widgets built from the old node, the container is the widget of the old node
+pub fn apply_patches<MSG, DSP>(
+    program: &DSP,
+    container: &Container,
+    old_node: &Node<MSG>,
+    patches: &Vec<Patch<MSG>>,
+) where
     MSG: Debug,
     DSP: Clone + Dispatch<MSG> + 'static,
 {
-    let nodes_to_patch = find_nodes(container, patches);
+    let nodes_to_patch = find_nodes(container, old_node, patches);
 
     for patch in patches {
         let patch_node_idx = patch.node_idx();
@@ -23,25 +40,54 @@ where
         match patch {
             Patch::AddAttributes(tag, _node_idx, attrs) => {
                 set_widget_attributes::<MSG>(tag, widget, attrs);
+                super::set_size_constraints(widget, attrs);
+                super::set_flex_attributes(widget, attrs);
+                super::set_grid_cell(widget, attrs);
+                super::set_layer_alignment(widget, attrs);
+                super::set_style(widget, attrs);
+                super::set_interaction_state(widget, attrs);
+                super::set_visibility(widget, attrs);
+                // the focus only moves when the focused attribute changed to true
+                // or the focus request changed
+                if focus::is_focused(attrs) {
+                    super::grab_focus(widget);
+                }
             }
             Patch::AppendChildren(tag, _node_idx, nodes) => {
                 if let Some(container) = widget.downcast_ref::<Container>() {
-                    for node in nodes {
+                    let existing_children = node_children(container).len();
+                    let container = &children_container(container);
+                    for (i, node) in nodes.iter().enumerate() {
                         if let Some(element) = node.as_element_ref() {
-                            let child =
-                                super::from_node(program, &element.tag, &node.get_attributes());
+                            let child_attrs = node.get_attributes();
+                            let child = super::from_node(program, &element.tag, &child_attrs);
                             let widget = child.as_widget().expect("must be a widget");
-                            container.add(widget);
+                            super::focus_when_shown(widget, &child_attrs);
+                            super::set_size_constraints(widget, &child_attrs);
+                            super::set_style(widget, &child_attrs);
+                            super::set_interaction_state(widget, &child_attrs);
+                            super::connect_key_events(program, widget, &child_attrs);
+                            super::connect_mouse_events(program, widget, &child_attrs);
+                            super::connect_focus_events(program, widget, &child_attrs);
+                            super::add_to_container(
+                                container,
+                                existing_children + i,
+                                widget,
+                                &child_attrs,
+                            );
                             widget.show();
+                            super::set_visibility(widget, &child_attrs);
                         }
                     }
                 }
             }
             Patch::TruncateChildren(tag, _node_idx, num_children_remaining) => {
                 if let Some(container) = widget.downcast_ref::<Container>() {
-                    let children = container.get_children();
+                    // destroying the children also drops what is kept for them,
+                    // such as their css providers and their place in a grid
+                    let children = node_children(container);
                     for i in *num_children_remaining..children.len() {
-                        container.remove(&children[i]);
+                        children[i].destroy();
                     }
                 }
             }
@@ -56,6 +102,17 @@ fn set_widget_attributes<MSG: 'static>(
     attrs: &Vec<Attribute<MSG>>,
 ) {
     match tag {
+        crate::Widget::Vbox | crate::Widget::Hbox => {
+            let gbox = widget.downcast_ref::<gtk::Box>().expect("must be a box");
+            super::set_box_layout(gbox, attrs);
+        }
+        // the window node is a box inside the window, the attributes of the window
+        // are set by update_windows from the whole node
+        crate::Widget::Window => {
+            if attrs.iter().any(|att| att.name == AttribKey::ApplicationId) {
+                warn!("application id can not be changed once the application has started");
+            }
+        }
         crate::Widget::Button => {
             let button = widget.downcast_ref::<Button>().expect("must be a button");
             for att in attrs {
@@ -67,18 +124,172 @@ fn set_widget_attributes<MSG: 'static>(
                 }
             }
         }
-        crate::Widget::TextArea => {
-            let scrolled_window = widget.downcast_ref::<ScrolledWindow>().expect("must be a scrolled window").get_children();
-            let child = scrolled_window.get(0).expect("must have 1 child");
-            let text_view = child
-                .downcast_ref::<TextView>()
-                .expect("must be a text_view");
+        crate::Widget::TextInput => {
+            let entry = widget.downcast_ref::<Entry>().expect("must be an entry");
             for att in attrs {
                 if let Some(value) = att.get_value() {
                     match att.name {
                         AttribKey::Value => {
-                            if let Some(buffer) = text_view.get_buffer() {
-                                buffer.set_text(&value.to_string());
+                            let text = value.to_string();
+                            // setting the same text would move the cursor
+                            if entry.get_text().as_deref() != Some(&*text) {
+                                entry.set_text(&text);
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            let patched = |key: AttribKey| attrs.iter().any(|att| att.name == key);
+            if patched(AttribKey::Password) || patched(AttribKey::Reveal) {
+                // the attribute which is not patched keeps the state of the entry
+                let password = find_value(AttribKey::Password, attrs)
+                    .map(|v| v.as_bool())
+                    .flatten()
+                    .unwrap_or_else(|| entry.get_input_purpose() == InputPurpose::Password);
+                let reveal = find_value(AttribKey::Reveal, attrs)
+                    .map(|v| v.as_bool())
+                    .flatten()
+                    .unwrap_or_else(|| {
+                        entry
+                            .get_icon_name(EntryIconPosition::Secondary)
+                            .is_some()
+                    });
+                super::set_password_mode(entry, password, reveal);
+            }
+        }
+        crate::Widget::Pages => {
+            let stack = widget.downcast_ref::<Stack>().expect("must be a stack");
+            for att in attrs {
+                if let Some(value) = att.get_value() {
+                    match att.name {
+                        AttribKey::VisibleChild => stack.set_visible_child_name(&value.to_string()),
+                        AttribKey::Transition => {
+                            if let Some(transition) = Transition::from_value(value) {
+                                stack.set_transition_type(super::stack_transition_type(transition));
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::Expander => {
+            let expander = widget
+                .downcast_ref::<Expander>()
+                .expect("must be an expander");
+            for att in attrs {
+                if let Some(value) = att.get_value() {
+                    match att.name {
+                        AttribKey::Label => expander.set_label(Some(&value.to_string())),
+                        AttribKey::Expanded => {
+                            let expanded = value.as_bool().unwrap_or(false);
+                            // avoid re-emitting the toggle event when the state didn't change
+                            if expander.get_expanded() != expanded {
+                                expander.set_expanded(expanded);
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::ToolButton | crate::Widget::ToolToggle => {
+            let tool_button = widget
+                .downcast_ref::<ToolButton>()
+                .expect("must be a tool button");
+            for att in attrs {
+                if let Some(value) = att.get_value() {
+                    match att.name {
+                        AttribKey::Label => tool_button.set_label(Some(&value.to_string())),
+                        AttribKey::Icon => super::set_tool_icon(tool_button, &value.to_string()),
+                        AttribKey::Value => {
+                            if let Some(tool_toggle) = widget.downcast_ref::<ToggleToolButton>() {
+                                let active = value.as_bool().unwrap_or(false);
+                                // avoid re-emitting the toggle event when the state didn't change
+                                if tool_toggle.get_active() != active {
+                                    tool_toggle.set_active(active);
+                                }
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::Link => {
+            let link = widget
+                .downcast_ref::<LinkButton>()
+                .expect("must be a link button");
+            for att in attrs {
+                if let Some(value) = att.get_value() {
+                    match att.name {
+                        AttribKey::Label => link.set_label(&value.to_string()),
+                        AttribKey::Uri => link.set_uri(&value.to_string()),
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::Separator => {
+            let separator = widget
+                .downcast_ref::<Separator>()
+                .expect("must be a separator");
+            for att in attrs {
+                if let Some(value) = att.get_value() {
+                    match att.name {
+                        AttribKey::Orientation => {
+                            if let Some(orientation) = Orientation::from_value(value) {
+                                separator.set_orientation(super::gtk_orientation(orientation));
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::Space => {
+            for att in attrs {
+                if let Some(size) = att.get_value().map(|v| v.as_f64()).flatten() {
+                    let (width, height) = widget.get_size_request();
+                    match att.name {
+                        AttribKey::Width => widget.set_size_request(size as i32, height),
+                        AttribKey::Height => widget.set_size_request(width, size as i32),
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::StatusSegment => {
+            let label = widget.downcast_ref::<Label>().expect("must be a label");
+            for att in attrs {
+                if let Some(value) = att.get_value() {
+                    match att.name {
+                        AttribKey::Value => label.set_text(&value.to_string()),
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::DatePicker => {
+            let menu_button = widget
+                .downcast_ref::<MenuButton>()
+                .expect("must be a menu button");
+            let calendar = menu_button
+                .get_popover()
+                .map(|popover| popover.get_child())
+                .flatten()
+                .map(|child| child.downcast::<Calendar>().ok())
+                .flatten()
+                .expect("must have a calendar");
+            for att in attrs {
+                if let Some(value) = att.get_value() {
+                    match att.name {
+                        AttribKey::Value => {
+                            let date = Date::parse(&value.to_string());
+                            menu_button.set_label(&super::date_label(date));
+                            if let Some(date) = date {
+                                super::select_date(&calendar, date);
                             }
                         }
                         _ => (),
@@ -86,29 +297,88 @@ fn set_widget_attributes<MSG: 'static>(
                 }
             }
         }
-        // Svg<ScrolledWindow<Image>>
-        crate::Widget::Svg => {
+        crate::Widget::TimePicker => {
+            // the hour and minute spin buttons are separated by a label
+            let spin_buttons: Vec<SpinButton> = widget
+                .downcast_ref::<EventBox>()
+                .expect("must be an event box")
+                .get_child()
+                .map(|child| child.downcast::<Container>().ok())
+                .flatten()
+                .expect("must have a box")
+                .get_children()
+                .into_iter()
+                .filter_map(|child| child.downcast::<SpinButton>().ok())
+                .collect();
+            for att in attrs {
+                if let Some(value) = att.get_value() {
+                    match att.name {
+                        AttribKey::Value => {
+                            let time = Time::parse(&value.to_string()).unwrap_or_default();
+                            for (spin_button, value) in
+                                spin_buttons.iter().zip(&[time.hour, time.minute])
+                            {
+                                // avoid re-emitting the change event when the time didn't change
+                                if spin_button.get_value_as_int() as u32 != *value {
+                                    spin_button.set_value(*value as f64);
+                                }
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::Checkbox | crate::Widget::Radio => {
+            let check_button = widget
+                .downcast_ref::<CheckButton>()
+                .expect("must be a check button");
+            for att in attrs {
+                if let Some(value) = att.get_value() {
+                    match att.name {
+                        AttribKey::Label => check_button.set_label(&value.to_string()),
+                        // the checked attribute takes precedence over the value
+                        AttribKey::Checked | AttribKey::Value => {
+                            let active = state::is_checked(attrs);
+                            // avoid re-emitting the toggle event when the state didn't change
+                            if check_button.get_active() != active {
+                                check_button.set_active(active);
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::Switch => {
+            let switch = widget.downcast_ref::<Switch>().expect("must be a switch");
+            for att in attrs {
+                if let Some(value) = att.get_value() {
+                    match att.name {
+                        AttribKey::Value => {
+                            let active = value.as_bool().unwrap_or(false);
+                            // avoid re-emitting the toggle event when the state didn't change
+                            if switch.get_active() != active {
+                                switch.set_active(active);
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::TextArea => {
             let scrolled_window = widget.downcast_ref::<ScrolledWindow>().expect("must be a scrolled window").get_children();
             let child = scrolled_window.get(0).expect("must have 1 child");
-            let view_port = child.downcast_ref::<Viewport>().expect("must be a viewport").get_children();
-            let vp_child = view_port.get(0).expect("must have 1 child");
-            let image = vp_child.downcast_ref::<Image>().expect("must be an image");
+            let text_view = child
+                .downcast_ref::<TextView>()
+                .expect("must be a text_view");
             for att in attrs {
                 if let Some(value) = att.get_value() {
                     match att.name {
-                        AttribKey::Data => {
-                            if let Some(bytes) = value.as_bytes() {
-                                    let pixbuf_loader =
-                                        PixbufLoader::new_with_mime_type("image/svg+xml")
-                                            .expect("error loader");
-                                    pixbuf_loader
-                                        .write(bytes)
-                                        .expect("Unable to write svg data into pixbuf_loader");
-                                    pixbuf_loader.close().expect("error creating pixbuf");
-                                    let pixbuf = pixbuf_loader.get_pixbuf();
-                                    image.set_from_pixbuf(Some(
-                                        &pixbuf.expect("error in pixbuf_loader"),
-                                    ));
+                        AttribKey::Value => {
+                            if let Some(buffer) = text_view.get_buffer() {
+                                buffer.set_text(&value.to_string());
                             }
                         }
                         _ => (),
@@ -116,49 +386,128 @@ fn set_widget_attributes<MSG: 'static>(
                 }
             }
         }
+        // Image<ScrolledWindow<Viewport<Image>>>
+        crate::Widget::Image | crate::Widget::Svg => {
+            let scroll_view = widget
+                .downcast_ref::<ScrolledWindow>()
+                .expect("must be a scrolled window");
+            let changes_image = attrs
+                .iter()
+                .any(|att| att.name == AttribKey::Data || att.name == AttribKey::Source);
+            if changes_image {
+                super::set_fitted_image(scroll_view, super::image_animation(tag, attrs));
+            }
+        }
         _ => {
             println!("todo for other widgets");
         }
     }
 }
 
-fn find_nodes<MSG>(root_node: &Container, patches: &[Patch<MSG>]) -> HashMap<usize, Widget> {
-    let mut nodes_to_find = HashSet::new();
-    let mut cur_node_idx = 0;
+fn find_nodes<MSG>(
+    root_node: &Container,
+    old_node: &Node<MSG>,
+    patches: &[Patch<MSG>],
+) -> HashMap<usize, Widget> {
+    let nodes_to_find: HashSet<usize> = patches.iter().map(|patch| patch.node_idx()).collect();
+    let mut nodes_to_patch = HashMap::new();
+    find_nodes_recursive(
+        root_node.upcast_ref(),
+        old_node,
+        &mut 0,
+        &nodes_to_find,
+        &mut nodes_to_patch,
+    );
+    nodes_to_patch
+}
+
+/// the container holding the children of a node,
+/// the children of an expander are in the box inside it
+fn children_container(container: &Container) -> Container {
+    container
+        .downcast_ref::<Expander>()
+        .map(|expander| expander.get_child())
+        .flatten()
+        .map(|content| content.downcast::<Container>().ok())
+        .flatten()
+        .unwrap_or_else(|| container.clone())
+}
 
-    for patch in patches {
-        nodes_to_find.insert(patch.node_idx());
+/// the widgets of the child nodes of a container, in the order of the nodes
+fn node_children(container: &Container) -> Vec<Widget> {
+    let container = children_container(container);
+    let mut children = match container.downcast_ref::<Grid>() {
+        Some(grid) => super::grid_children(grid),
+        None => container.get_children(),
+    };
+    // the first child of a statusbar is its own message area
+    if container.downcast_ref::<Statusbar>().is_some() && !children.is_empty() {
+        children.remove(0);
     }
-    find_nodes_recursive(root_node, &mut cur_node_idx, &nodes_to_find)
+    // the widgets other than the tool items are wrapped in a tool item
+    if container.downcast_ref::<Toolbar>().is_some() {
+        children = children.into_iter().map(tool_item_content).collect();
+    }
+    children
 }
 
-fn find_nodes_recursive(
-    root_node: &Container,
+/// the widget of a node in a toolbar, the tool item wrapping it when it is not a tool item
+fn tool_item_content(item: Widget) -> Widget {
+    if item.get_type() != ToolItem::static_type() {
+        return item;
+    }
+    item.downcast_ref::<ToolItem>()
+        .map(|wrapper| wrapper.get_child())
+        .flatten()
+        .unwrap_or(item)
+}
+
+/// Find the widgets of the nodes to patch, walking the widgets along the old node.
+/// Only the widgets of the nodes with children are traversed, so the widgets gtk puts
+/// inside the other widgets such as the text view of a textarea are not taken for nodes.
+fn find_nodes_recursive<MSG>(
+    widget: &Widget,
+    node: &Node<MSG>,
     cur_node_idx: &mut usize,
     nodes_to_find: &HashSet<usize>,
-) -> HashMap<usize, Widget> {
-    let mut nodes_to_patch: HashMap<usize, Widget> = HashMap::new();
-
-    let is_gbox = root_node.downcast_ref::<gtk::Box>().is_some();
-    let is_paned = root_node.downcast_ref::<gtk::Paned>().is_some();
-    // prevent other container other than gtk::Box to be traverse otherwise widget such as textarea or textinput will
-    // be traverse
-    if is_gbox || is_paned {
-        let children = root_node.get_children();
-        let child_node_count = children.len();
-
-        for child in children {
-            *cur_node_idx += 1;
-            if nodes_to_find.get(&cur_node_idx).is_some() {
-                let widget: Widget = child.clone().upcast();
-                nodes_to_patch.insert(*cur_node_idx, widget);
-            }
-            if let Some(container) = child.downcast_ref::<Container>() {
-                let child_nodes_to_patch =
-                    find_nodes_recursive(container, cur_node_idx, nodes_to_find);
-                nodes_to_patch.extend(child_nodes_to_patch);
-            }
+    nodes_to_patch: &mut HashMap<usize, Widget>,
+) {
+    if nodes_to_find.contains(cur_node_idx) {
+        nodes_to_patch.insert(*cur_node_idx, widget.clone());
+    }
+    let child_nodes = match node.as_element_ref() {
+        Some(element) if !element.children.is_empty() => &element.children,
+        _ => return,
+    };
+    let child_widgets = widget
+        .downcast_ref::<Container>()
+        .map(node_children)
+        .unwrap_or_default();
+    for (index, child_node) in child_nodes.iter().enumerate() {
+        *cur_node_idx += 1;
+        match child_widgets.get(index) {
+            Some(child_widget) => find_nodes_recursive(
+                child_widget,
+                child_node,
+                cur_node_idx,
+                nodes_to_find,
+                nodes_to_patch,
+            ),
+            // a node without a widget still numbers its children
+            None => *cur_node_idx += descendant_count(child_node),
         }
     }
-    nodes_to_patch
+}
+
+/// the number of nodes under a node
+fn descendant_count<MSG>(node: &Node<MSG>) -> usize {
+    node.as_element_ref()
+        .map(|element| {
+            element
+                .children
+                .iter()
+                .map(|child| 1 + descendant_count(child))
+                .sum()
+        })
+        .unwrap_or(0)
 }
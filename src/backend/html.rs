@@ -1,15 +1,42 @@
 use crate::{
-    util, widget::attribute::find_value, AttribKey, Attribute, Backend, Component, Widget,
+    util,
+    widget::{
+        attribute::{find_callback, find_value},
+        focus::{self, FocusTarget},
+        key::{self, Modifiers},
+        layout::{
+            self, Align, Alignment, Fit, Flex, GridCell, Insets, SizeConstraints, Transition,
+        },
+        state,
+        style::{ColorScheme, Style, Theme},
+        window::WindowConfig,
+    },
+    AttribKey, Attribute, Backend, Component, Widget,
 };
 use image::ImageFormat;
 use sauron::{
-    html::{attributes::*, div, events::mapper, img, input, text},
+    html::{
+        a,
+        attributes::*,
+        details, div,
+        events::{self, mapper},
+        img, input, summary, text,
+    },
     prelude::*,
     Component as SauronComponent, DomUpdater, Program,
 };
-use sauron_vdom::Callback;
-use std::{cell::RefCell, fmt::Debug, marker::PhantomData, rc::Rc};
-use wasm_bindgen::JsCast;
+use sauron_vdom::{event::InputEvent, Callback};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    fmt::Debug,
+    marker::PhantomData,
+    rc::Rc,
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{
+    HtmlDetailsElement, HtmlElement, HtmlInputElement, HtmlTextAreaElement, KeyboardEvent, UiEvent,
+};
 
 pub struct HtmlApp<APP, MSG>
 where
@@ -17,6 +44,11 @@ where
     APP: Component<MSG> + 'static,
 {
     app: APP,
+    /// the node which asked for the focus in the last view
+    focus_target: RefCell<Option<FocusTarget>>,
+    /// whether the document listeners are installed, they send the changes of the color scheme
+    /// to the windows listening to them and guard the password inputs
+    document_listened: Cell<bool>,
     _phantom_data: PhantomData<MSG>,
 }
 
@@ -36,6 +68,8 @@ where
     fn new(app: APP) -> Self {
         HtmlApp {
             app,
+            focus_target: RefCell::new(None),
+            document_listened: Cell::new(false),
             _phantom_data: PhantomData,
         }
     }
@@ -53,6 +87,25 @@ where
 
     fn view(&self) -> sauron::Node<MSG> {
         let view = self.app.view();
+        // the document takes the place of the window when the root node is a window
+        if let Some(config) = WindowConfig::from_root(&view) {
+            if let Some(title) = &config.title {
+                set_document_title(title);
+            }
+            if let Some(icon) = &config.icon {
+                set_document_icon(icon);
+            }
+        }
+        let focus_target = focus::focus_target(&view);
+        if focus_target.is_some() && focus_target != *self.focus_target.borrow() {
+            focus_after_render();
+        }
+        *self.focus_target.borrow_mut() = focus_target;
+        if !self.document_listened.replace(true) {
+            watch_color_scheme();
+            guard_passwords();
+            guard_read_only();
+        }
         let html_view = widget_tree_to_html_node(view);
         html_view
     }
@@ -72,50 +125,215 @@ where
     }
 }
 
-/// convert Widget into an equivalent html node
-fn widget_to_html<MSG>(widget: &Widget, attrs: Vec<Attribute<MSG>>) -> sauron::Node<MSG>
+/// convert Widget into an equivalent html node,
+/// the path is the index of the node and its ancestors in their parent
+fn widget_to_html<MSG>(
+    widget: &Widget,
+    attrs: Vec<Attribute<MSG>>,
+    path: &[usize],
+) -> sauron::Node<MSG>
 where
     MSG: Clone + Debug + 'static,
 {
     match widget {
-        Widget::Vbox => div(
+        // the windows of the application are floating panels positioned relative to it
+        Widget::Application => div(
+            vec![styles([("position", "relative"), ("min-height", "100vh")])],
+            vec![],
+        ),
+        Widget::Window => {
+            let config = WindowConfig::from_attrs(&attrs);
+            let mut window_styles = vec![
+                ("display", "flex".to_string()),
+                ("flex-direction", "column".to_string()),
+            ];
+            if let Some(min_width) = config.min_width {
+                window_styles.push(("min-width", format!("{}px", min_width)));
+            }
+            if let Some(min_height) = config.min_height {
+                window_styles.push(("min-height", format!("{}px", min_height)));
+            }
+            // the form controls of the browser follow the color scheme too
+            if find_value(AttribKey::DarkTheme, &attrs).is_some() {
+                window_styles.push(("color-scheme", "light dark".to_string()));
+            }
+            let mut window_attributes = vec![styles(window_styles)];
+            if let Some(cb) = find_callback(AttribKey::ColorSchemeEvent, &attrs) {
+                window_attributes.extend(on_color_scheme(cb.clone()));
+            }
+            div(window_attributes, vec![])
+        }
+        Widget::Vbox => div(vec![styles(box_styles("column", &attrs))], vec![]),
+        Widget::Hbox => div(vec![styles(box_styles("row", &attrs))], vec![]),
+        //TODO: vpane and hpane should be draggable
+        Widget::Vpane => div(
             vec![styles(vec![
                 ("display", "flex"),
                 ("flex-direction", "column"),
             ])],
             vec![],
         ),
-        Widget::Hbox => div(
+        Widget::Hpane => div(
             vec![styles(vec![("display", "flex"), ("flex-direction", "row")])],
             vec![],
         ),
-        //TODO: vpane and hpane should be draggable
-        Widget::Vpane => div(
-            vec![styles(vec![
+        Widget::Grid => div(vec![styles(vec![("display", "grid")])], vec![]),
+        // the first child stays in the flow to give the stack its size
+        Widget::Stack => div(vec![styles(vec![("position", "relative")])], vec![]),
+        // all the pages occupy the same grid cell, so switching pages doesn't resize it
+        Widget::Pages => div(
+            vec![styles(vec![("display", "grid"), ("overflow", "hidden")])],
+            vec![],
+        ),
+        Widget::Toolbar => div(
+            vec![styles([
                 ("display", "flex"),
-                ("flex-direction", "column"),
+                ("flex-direction", "row"),
+                ("align-items", "center"),
+                ("padding", "2px 4px"),
+                ("border-bottom", "1px solid #cccccc"),
             ])],
             vec![],
         ),
-        Widget::Hpane => div(
-            vec![styles(vec![("display", "flex"), ("flex-direction", "row")])],
+        // pushed to the bottom of the window, which lays out its children in a flex column
+        Widget::StatusBar => div(
+            vec![styles([
+                ("display", "flex"),
+                ("flex-direction", "row"),
+                ("align-items", "center"),
+                ("margin-top", "auto"),
+                ("padding", "2px 4px"),
+                ("border-top", "1px solid #cccccc"),
+                ("font-size", "0.85em"),
+            ])],
             vec![],
         ),
         Widget::Button => {
             let label = find_value(AttribKey::Label, &attrs)
                 .map(|v| v.to_string())
                 .unwrap_or(String::new());
+            let state_attrs = state_attributes(widget, &attrs);
 
             let attributes = attrs
                 .into_iter()
                 .filter_map(|att| match att.name {
-                    AttribKey::ClickEvent => {
-                        att.take_callback().map(|cb| onclick(move |ev| cb.emit(ev)))
-                    }
+                    AttribKey::ClickEvent => att.take_callback().map(on_click),
                     _ => None,
                 })
                 .collect();
-            input(vec![r#type("button"), value(label)], vec![]).add_attributes(attributes)
+            input(vec![r#type("button"), value(label)], vec![])
+                .add_attributes(state_attrs)
+                .add_attributes(attributes)
+        }
+        Widget::DatePicker | Widget::TimePicker => {
+            let input_type = match widget {
+                Widget::DatePicker => "date",
+                _ => "time",
+            };
+            let txt_value = find_value(AttribKey::Value, &attrs)
+                .map(|v| v.to_string())
+                .unwrap_or(String::new());
+            let attributes = attrs
+                .into_iter()
+                .filter_map(|att| match att.name {
+                    AttribKey::ChangeEvent => att.take_callback().map(on_value_change),
+                    _ => None,
+                })
+                .collect();
+            input(vec![r#type(input_type), value(txt_value)], vec![]).add_attributes(attributes)
+        }
+        Widget::Link => {
+            let label = find_value(AttribKey::Label, &attrs)
+                .map(|v| v.to_string())
+                .unwrap_or(String::new());
+            let uri = find_value(AttribKey::Uri, &attrs)
+                .map(|v| v.to_string())
+                .unwrap_or(String::from("#"));
+
+            let attributes = attrs
+                .into_iter()
+                .filter_map(|att| match att.name {
+                    AttribKey::ClickEvent => att.take_callback().map(on_link_click),
+                    _ => None,
+                })
+                .collect();
+            a(vec![href(uri), rel("noopener")], vec![text(label)]).add_attributes(attributes)
+        }
+        Widget::ToolButton => {
+            let content = tool_item_content(&attrs);
+            let attributes = attrs
+                .into_iter()
+                .filter_map(|att| match att.name {
+                    AttribKey::ClickEvent => att.take_callback().map(on_click),
+                    _ => None,
+                })
+                .collect();
+            button(vec![styles(tool_item_styles(false))], content).add_attributes(attributes)
+        }
+        Widget::ToolToggle => {
+            let active = find_value(AttribKey::Value, &attrs)
+                .map(|v| v.as_bool())
+                .flatten()
+                .unwrap_or(false);
+            let content = tool_item_content(&attrs);
+            let attributes = attrs
+                .into_iter()
+                .filter_map(|att| match att.name {
+                    AttribKey::ToggleEvent => att.take_callback().map(|cb| {
+                        onclick(move |_| cb.emit(InputEvent::new((!active).to_string())))
+                    }),
+                    _ => None,
+                })
+                .collect();
+            button(
+                vec![
+                    styles(tool_item_styles(active)),
+                    attr("aria-pressed", active.to_string()),
+                ],
+                content,
+            )
+            .add_attributes(attributes)
+        }
+        Widget::Separator => match layout::Orientation::find(&attrs) {
+            layout::Orientation::Horizontal => hr(
+                vec![styles([
+                    ("align-self", "stretch"),
+                    ("margin", "4px 0"),
+                    ("border", "none"),
+                    ("border-top", "1px solid #cccccc"),
+                ])],
+                vec![],
+            ),
+            layout::Orientation::Vertical => div(
+                vec![styles([
+                    ("align-self", "stretch"),
+                    ("margin", "0 4px"),
+                    ("border-left", "1px solid #cccccc"),
+                ])],
+                vec![],
+            ),
+        },
+        Widget::Space => div(vec![styles(space_styles(&attrs))], vec![]),
+        Widget::ToolSeparator => div(
+            vec![styles([
+                ("align-self", "stretch"),
+                ("width", "1px"),
+                ("margin", "2px 4px"),
+                ("background", "#cccccc"),
+            ])],
+            vec![],
+        ),
+        Widget::StatusSegment => {
+            let txt_value = find_value(AttribKey::Value, &attrs)
+                .map(|v| v.to_string())
+                .unwrap_or(String::new());
+            span(
+                vec![styles([
+                    ("padding", "0 8px"),
+                    ("border-right", "1px solid #cccccc"),
+                ])],
+                vec![text(txt_value)],
+            )
         }
         Widget::Paragraph => {
             let txt_value = find_value(AttribKey::Value, &attrs)
@@ -127,6 +345,15 @@ where
             let txt_value = find_value(AttribKey::Value, &attrs)
                 .map(|v| v.to_string())
                 .unwrap_or(String::new());
+            let password = find_value(AttribKey::Password, &attrs)
+                .map(|v| v.as_bool())
+                .flatten()
+                .unwrap_or(false);
+            let reveal = find_value(AttribKey::Reveal, &attrs)
+                .map(|v| v.as_bool())
+                .flatten()
+                .unwrap_or(false);
+            let state_attrs = state_attributes(widget, &attrs);
             let attributes = attrs
                 .into_iter()
                 .filter_map(|att| match att.name {
@@ -136,12 +363,42 @@ where
                     _ => None,
                 })
                 .collect();
-            input(vec![r#type("text"), value(txt_value)], vec![]).add_attributes(attributes)
+            if password {
+                let password_input = input(
+                    vec![
+                        r#type("password"),
+                        value(txt_value),
+                        attr(PASSWORD_MARKER, ""),
+                    ],
+                    vec![],
+                )
+                .add_attributes(state_attrs)
+                .add_attributes(attributes);
+                if reveal {
+                    span(
+                        vec![attr(PASSWORD_BOX_MARKER, "")],
+                        vec![
+                            password_input,
+                            input(
+                                vec![r#type("button"), value("👁"), attr(REVEAL_MARKER, "")],
+                                vec![],
+                            ),
+                        ],
+                    )
+                } else {
+                    password_input
+                }
+            } else {
+                input(vec![r#type("text"), value(txt_value)], vec![])
+                    .add_attributes(state_attrs)
+                    .add_attributes(attributes)
+            }
         }
         Widget::TextArea => {
             let txt_value = find_value(AttribKey::Value, &attrs)
                 .map(|v| v.to_string())
                 .unwrap_or(String::new());
+            let state_attrs = state_attributes(widget, &attrs);
             let attributes = attrs
                 .into_iter()
                 .filter_map(|att| match att.name {
@@ -151,105 +408,1074 @@ where
                     _ => None,
                 })
                 .collect();
-            textarea(vec![value(&txt_value)], vec![text(txt_value)]).add_attributes(attributes)
+            textarea(vec![value(&txt_value)], vec![text(txt_value)])
+                .add_attributes(state_attrs)
+                .add_attributes(attributes)
         }
         Widget::Checkbox => {
             let cb_label = find_value(AttribKey::Label, &attrs)
                 .map(|v| v.to_string())
                 .unwrap_or(String::new());
-            let cb_value = find_value(AttribKey::Value, &attrs)
-                .map(|v| v.as_bool())
-                .flatten()
-                .unwrap_or(false);
-            let checked = attrs_flag([("checked", "checked", cb_value)]);
+            let checked = attrs_flag([("checked", "checked", state::is_checked(&attrs))]);
+            let state_attrs = state_attributes(widget, &attrs);
+            let attributes = attrs
+                .into_iter()
+                .filter_map(|att| match att.name {
+                    AttribKey::ToggleEvent => att.take_callback().map(on_checked_change),
+                    _ => None,
+                })
+                .collect();
 
             div(
                 vec![],
                 vec![
-                    input(vec![type_("checkbox")], vec![]).add_attributes(checked),
+                    input(vec![type_("checkbox")], vec![])
+                        .add_attributes(checked)
+                        .add_attributes(state_attrs)
+                        .add_attributes(attributes),
                     label(vec![], vec![text(cb_label)]),
                 ],
             )
         }
-        Widget::Radio => {
-            let cb_label = find_value(AttribKey::Label, &attrs)
+        // the children are appended after the summary
+        Widget::Expander => {
+            let header = find_value(AttribKey::Label, &attrs)
                 .map(|v| v.to_string())
                 .unwrap_or(String::new());
+            let expanded = find_value(AttribKey::Expanded, &attrs)
+                .map(|v| v.as_bool())
+                .flatten()
+                .unwrap_or(false);
+            let open = attrs_flag([("open", "open", expanded)]);
+            let attributes = attrs
+                .into_iter()
+                .filter_map(|att| match att.name {
+                    AttribKey::ToggleEvent => att.take_callback().map(on_details_toggle),
+                    _ => None,
+                })
+                .collect();
+            details(vec![], vec![summary(vec![], vec![text(header)])])
+                .add_attributes(open)
+                .add_attributes(attributes)
+        }
+        Widget::Switch => {
             let cb_value = find_value(AttribKey::Value, &attrs)
                 .map(|v| v.as_bool())
                 .flatten()
                 .unwrap_or(false);
             let checked = attrs_flag([("checked", "checked", cb_value)]);
+            // the knob is drawn with a gradient since inline styles can not use pseudo elements
+            let knob = if cb_value {
+                "radial-gradient(circle at 70% 50%, white 0.45em, #4a90d9 0.5em)"
+            } else {
+                "radial-gradient(circle at 30% 50%, white 0.45em, #bbbbbb 0.5em)"
+            };
+            let attributes = attrs
+                .into_iter()
+                .filter_map(|att| match att.name {
+                    AttribKey::ToggleEvent => att.take_callback().map(on_checked_change),
+                    _ => None,
+                })
+                .collect();
+            input(
+                vec![
+                    type_("checkbox"),
+                    attr("role", "switch"),
+                    styles([
+                        ("appearance", "none"),
+                        ("-webkit-appearance", "none"),
+                        ("width", "2.5em"),
+                        ("height", "1.4em"),
+                        ("border-radius", "0.7em"),
+                        ("cursor", "pointer"),
+                        ("background", knob),
+                    ]),
+                ],
+                vec![],
+            )
+            .add_attributes(checked)
+            .add_attributes(attributes)
+        }
+        Widget::Radio => {
+            let cb_label = find_value(AttribKey::Label, &attrs)
+                .map(|v| v.to_string())
+                .unwrap_or(String::new());
+            let checked = attrs_flag([("checked", "checked", state::is_checked(&attrs))]);
+            let state_attrs = state_attributes(widget, &attrs);
+            let attributes = attrs
+                .into_iter()
+                .filter_map(|att| match att.name {
+                    AttribKey::ToggleEvent => att.take_callback().map(on_checked_change),
+                    _ => None,
+                })
+                .collect();
             div(
                 vec![],
                 vec![
-                    input(vec![type_("radio")], vec![]).add_attributes(checked),
+                    input(
+                        vec![type_("radio"), attr("name", radio_group(path))],
+                        vec![],
+                    )
+                    .add_attributes(checked)
+                    .add_attributes(state_attrs)
+                    .add_attributes(attributes),
                     label(vec![], vec![text(cb_label)]),
                 ],
             )
         }
-        Widget::Image => {
-            let empty = vec![];
-            let bytes = find_value(AttribKey::Data, &attrs)
-                .map(|v| v.as_bytes())
-                .flatten()
-                .unwrap_or(&empty);
+        Widget::Image | Widget::Svg => img(
+            vec![styles(image_styles(&attrs)), src(image_src(widget, &attrs))],
+            vec![],
+        ),
+    }
+}
 
-            let mime_type = util::image_mime_type(bytes).expect("unsupported image");
-            img(
-                vec![
-                    styles([
-                        ("width", "100%"),
-                        ("height", "auto"),
-                        ("max-width", "800px"),
-                    ]),
-                    src(format!(
-                        "data:{};base64,{}",
-                        mime_type,
-                        base64::encode(bytes)
-                    )),
-                ],
+/// the name the browser groups the radios of a container by, it is made from the path
+/// of the container so the radios of other containers are not in the group
+fn radio_group(path: &[usize]) -> String {
+    let container = &path[..path.len().saturating_sub(1)];
+    let indices: Vec<String> = container.iter().map(|index| index.to_string()).collect();
+    format!("radio-group-{}", indices.join("-"))
+}
+
+/// the image formats browsers display without converting them, animated gifs are played
+const BROWSER_FORMATS: &[ImageFormat] = &[
+    ImageFormat::Png,
+    ImageFormat::Jpeg,
+    ImageFormat::Gif,
+    ImageFormat::WebP,
+    ImageFormat::Bmp,
+    ImageFormat::Ico,
+];
+
+/// The source of an image is used as is and left for the browser to load and cache,
+/// the data of an image is embedded as a data url
+fn image_src<MSG>(widget: &Widget, attrs: &Vec<Attribute<MSG>>) -> String
+where
+    MSG: 'static,
+{
+    if let Some(source) = find_value(AttribKey::Source, attrs) {
+        return source.to_string();
+    }
+    let empty = vec![];
+    let bytes = find_value(AttribKey::Data, attrs)
+        .map(|v| v.as_bytes())
+        .flatten()
+        .unwrap_or(&empty);
+    let displayable = match widget {
+        Widget::Svg => Some((Cow::Borrowed(&bytes[..]), "image/svg+xml")),
+        _ => util::displayable_image(bytes, BROWSER_FORMATS),
+    };
+    match displayable {
+        Some((bytes, mime_type)) => {
+            format!("data:{};base64,{}", mime_type, base64::encode(&bytes))
+        }
+        None => String::new(),
+    }
+}
+
+/// size the image to its width and height, a missing side follows the aspect ratio of the image
+fn image_styles<MSG>(attrs: &Vec<Attribute<MSG>>) -> Vec<(&'static str, String)>
+where
+    MSG: 'static,
+{
+    let size = |key| {
+        find_value(key, attrs)
+            .map(|v| v.as_f64())
+            .flatten()
+            .map(|size| format!("{}px", size))
+    };
+    let width = size(AttribKey::Width);
+    let height = size(AttribKey::Height);
+    vec![
+        (
+            "width",
+            match (&width, &height) {
+                (None, None) => "100%".to_string(),
+                _ => width.unwrap_or_else(|| "auto".to_string()),
+            },
+        ),
+        ("height", height.unwrap_or_else(|| "auto".to_string())),
+        ("object-fit", Fit::find(attrs).as_str().to_string()),
+    ]
+}
+
+/// the flex styles of a row or a column from its layout attributes
+fn box_styles<MSG>(
+    direction: &'static str,
+    attrs: &Vec<Attribute<MSG>>,
+) -> Vec<(&'static str, String)>
+where
+    MSG: 'static,
+{
+    let justify_content = match Alignment::find(attrs) {
+        Alignment::Start => "flex-start",
+        Alignment::Center => "center",
+        Alignment::End => "flex-end",
+    };
+    let align_items = match Align::find(AttribKey::CrossAlignment, attrs) {
+        Align::Fill => "stretch",
+        Align::Start => "flex-start",
+        Align::Center => "center",
+        Align::End => "flex-end",
+    };
+    let mut box_styles = vec![
+        ("display", "flex".to_string()),
+        ("flex-direction", direction.to_string()),
+        ("justify-content", justify_content.to_string()),
+        ("align-items", align_items.to_string()),
+    ];
+    let spacing = layout::spacing(attrs);
+    if spacing > 0.0 {
+        box_styles.push(("gap", format!("{}px", spacing)));
+    }
+    let padding = Insets::find(AttribKey::Padding, attrs);
+    if !padding.is_empty() {
+        box_styles.push(("padding", css_insets(padding)));
+    }
+    let margin = Insets::find(AttribKey::Margin, attrs);
+    if !margin.is_empty() {
+        box_styles.push(("margin", css_insets(margin)));
+    }
+    box_styles
+}
+
+fn css_insets(insets: Insets) -> String {
+    format!(
+        "{}px {}px {}px {}px",
+        insets.top, insets.end, insets.bottom, insets.start
+    )
+}
+
+/// a space with a size doesn't shrink, otherwise it takes the space left in its parent
+fn space_styles<MSG>(attrs: &Vec<Attribute<MSG>>) -> Vec<(&'static str, String)>
+where
+    MSG: 'static,
+{
+    if layout::is_flexible_space(attrs) {
+        return vec![("flex", "1 1 0".to_string())];
+    }
+    let size = |key| {
+        find_value(key, attrs)
+            .map(|v| v.as_f64())
+            .flatten()
+            .map(|size| format!("{}px", size))
+    };
+    let mut styles = vec![("flex-shrink", "0".to_string())];
+    if let Some(width) = size(AttribKey::Width) {
+        styles.push(("width", width));
+    }
+    if let Some(height) = size(AttribKey::Height) {
+        styles.push(("height", height));
+    }
+    styles
+}
+
+/// the elements that can take the focus by themselves
+const FOCUSABLE: &str = "input, textarea, button, select, summary, a[href]";
+
+/// the event sent to the windows listening to the color scheme
+const COLOR_SCHEME_EVENT: &str = "colorschemechange";
+
+/// the marker of the password inputs, their text can't be copied
+const PASSWORD_MARKER: &str = "data-password";
+
+/// the marker of the span around a password input and its reveal button
+const PASSWORD_BOX_MARKER: &str = "data-password-box";
+
+/// the marker of the button which reveals the password input in the same password box
+const REVEAL_MARKER: &str = "data-reveal";
+
+/// the marker of the read only checkboxes and radios, their clicks are canceled
+const READ_ONLY_MARKER: &str = "data-read-only";
+
+/// the tab index of the node and a marker for the node which asks for the focus
+fn focus_attributes<MSG>(attrs: &Vec<Attribute<MSG>>) -> Vec<sauron::Attribute<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let mut attributes = vec![];
+    if let Some(tab_index) = focus::tab_index(attrs) {
+        attributes.push(attr("tabindex", tab_index.to_string()));
+    }
+    if focus::is_focused(attrs) {
+        attributes.push(attr("data-focused", "true"));
+    }
+    attributes
+}
+
+/// the key listeners of a widget, the keys pressed in the elements inside it bubble up to it
+fn key_attributes<MSG>(attrs: &Vec<Attribute<MSG>>) -> Vec<sauron::Attribute<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let mut attributes = vec![];
+    if let Some(cb) = find_callback(AttribKey::KeyDownEvent, attrs) {
+        let cb = cb.clone();
+        attributes.push(events::on("keydown", move |event: sauron::Event| {
+            cb.emit(keyboard_event(&event))
+        }));
+    }
+    if let Some(cb) = find_callback(AttribKey::KeyUpEvent, attrs) {
+        let cb = cb.clone();
+        attributes.push(events::on("keyup", move |event: sauron::Event| {
+            cb.emit(keyboard_event(&event))
+        }));
+    }
+    attributes
+}
+
+/// the key event of a browser keyboard event, with the physical key in its code
+fn keyboard_event(event: &sauron::Event) -> sauron_vdom::Event {
+    match event.dyn_ref::<KeyboardEvent>() {
+        Some(event) => {
+            let modifiers = Modifiers {
+                ctrl: event.ctrl_key(),
+                shift: event.shift_key(),
+                alt: event.alt_key(),
+                meta: event.meta_key(),
+            };
+            key::key_event(event.key(), event.code(), modifiers, event.repeat())
+        }
+        None => key::key_event(
+            "Unidentified".to_string(),
+            "Unidentified".to_string(),
+            Modifiers::default(),
+            false,
+        ),
+    }
+}
+
+/// The focus and blur listeners of a widget. Focusin and focusout are listened to,
+/// they bubble so the input inside the element of a checkbox or a radio reaches them.
+fn focus_listener_attributes<MSG>(attrs: &Vec<Attribute<MSG>>) -> Vec<sauron::Attribute<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let mut attributes = vec![];
+    if let Some(cb) = find_callback(AttribKey::FocusEvent, attrs) {
+        let cb = cb.clone();
+        attributes.push(events::on("focusin", move |event: sauron::Event| {
+            cb.emit(InputEvent::new(focus_value(&event)))
+        }));
+    }
+    if let Some(cb) = find_callback(AttribKey::BlurEvent, attrs) {
+        let cb = cb.clone();
+        attributes.push(events::on("focusout", move |event: sauron::Event| {
+            cb.emit(InputEvent::new(focus_value(&event)))
+        }));
+    }
+    attributes
+}
+
+/// the value of the element taking or losing the focus
+fn focus_value(event: &sauron::Event) -> String {
+    let target = match event.target() {
+        Some(target) => target,
+        None => return String::new(),
+    };
+    if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
+        match &*input.type_() {
+            "checkbox" | "radio" => input.checked().to_string(),
+            _ => input.value(),
+        }
+    } else if let Some(textarea) = target.dyn_ref::<HtmlTextAreaElement>() {
+        textarea.value()
+    } else {
+        String::new()
+    }
+}
+
+/// the mouse listeners of a widget, the offset of the event is the position in the element
+/// and the client position is the position in the page
+fn mouse_attributes<MSG>(attrs: &Vec<Attribute<MSG>>) -> Vec<sauron::Attribute<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+{
+    [
+        (AttribKey::MouseDownEvent, "mousedown"),
+        (AttribKey::MouseUpEvent, "mouseup"),
+        (AttribKey::MouseMoveEvent, "mousemove"),
+        (AttribKey::DoubleClickEvent, "dblclick"),
+    ]
+    .iter()
+    .filter_map(|(key, event_name)| {
+        find_callback(key.clone(), attrs).map(|cb| {
+            let cb = cb.clone();
+            events::on(*event_name, move |event: sauron::Event| {
+                cb.emit(counted_mouse_event(event))
+            })
+        })
+    })
+    .collect()
+}
+
+/// the mouse event of a browser mouse event, with the click count the browser puts in detail
+fn counted_mouse_event(event: sauron::Event) -> sauron_vdom::Event {
+    let click_count = event
+        .dyn_ref::<UiEvent>()
+        .map(|event| event.detail())
+        .unwrap_or(0);
+    match mapper::mouse_event_mapper(event) {
+        sauron_vdom::Event::MouseEvent(mouse_event) => {
+            sauron_vdom::Event::MouseEvent(sauron_vdom::event::MouseEvent {
+                detail: click_count,
+                ..mouse_event
+            })
+        }
+        event => event,
+    }
+}
+
+/// The disabled and readonly attributes of a form control. Browsers only make the text
+/// of a control read only, the clicks of a read only checkbox or radio are canceled
+/// by guard_read_only instead.
+fn state_attributes<MSG>(
+    widget: &Widget,
+    attrs: &Vec<Attribute<MSG>>,
+) -> Vec<sauron::Attribute<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let read_only = state::is_read_only(attrs);
+    let mut attributes = attrs_flag([
+        ("disabled", "disabled", state::is_disabled(attrs)),
+        ("readonly", "readonly", read_only),
+    ]);
+    if read_only && (*widget == Widget::Checkbox || *widget == Widget::Radio) {
+        attributes.push(attr("aria-readonly", "true"));
+        attributes.push(attr(READ_ONLY_MARKER, ""));
+    }
+    attributes
+}
+
+/// Focus the element which asks for the focus once the view is patched into the document.
+/// When the element is a wrapper, such as the div of a checkbox, the control inside it is focused.
+fn focus_after_render() {
+    let focus = Closure::once_into_js(|| {
+        let element = web_sys::window()
+            .map(|window| window.document())
+            .flatten()
+            .map(|document| document.query_selector("[data-focused]").ok())
+            .flatten()
+            .flatten();
+        if let Some(element) = element {
+            let inner = if element.matches(FOCUSABLE).unwrap_or(false) {
+                None
+            } else {
+                element.query_selector(FOCUSABLE).ok().flatten()
+            };
+            let target = inner.unwrap_or(element);
+            if let Ok(target) = target.dyn_into::<HtmlElement>() {
+                if let Err(e) = target.focus() {
+                    log::error!("unable to focus element: {:?}", e);
+                }
+            }
+        }
+    });
+    if let Some(window) = web_sys::window() {
+        if let Err(e) = window.set_timeout_with_callback(focus.unchecked_ref()) {
+            log::error!("unable to schedule focus: {:?}", e);
+        }
+    }
+}
+
+/// the color scheme of the `prefers-color-scheme` media query
+fn preferred_color_scheme() -> ColorScheme {
+    let prefers_dark = web_sys::window()
+        .map(|window| window.match_media("(prefers-color-scheme: dark)").ok())
+        .flatten()
+        .flatten()
+        .map(|media| media.matches())
+        .unwrap_or(false);
+    if prefers_dark {
+        ColorScheme::Dark
+    } else {
+        ColorScheme::Light
+    }
+}
+
+/// Prevent copying or cutting the text of the password inputs, even when it is revealed,
+/// and switch a password input between masked and revealed when its reveal button is clicked.
+///
+/// The listeners are on the document, since preventing the copy and toggling the input
+/// type don't emit a message of the app which a listener on the node would have to return.
+/// The inputs are found by their markers, so no inline script is needed.
+fn guard_passwords() {
+    fn closest(event: &web_sys::Event, selector: &str) -> Option<web_sys::Element> {
+        event
+            .target()
+            .map(|target| target.dyn_into::<web_sys::Element>().ok())
+            .flatten()
+            .map(|element| element.closest(selector).ok())
+            .flatten()
+            .flatten()
+    }
+    let document = match web_sys::window().map(|window| window.document()).flatten() {
+        Some(document) => document,
+        None => return,
+    };
+    let password_selector = format!("[{}]", PASSWORD_MARKER);
+    let on_clipboard = Closure::wrap(Box::new(move |event: web_sys::Event| {
+        if closest(&event, &password_selector).is_some() {
+            event.prevent_default();
+        }
+    }) as Box<dyn FnMut(web_sys::Event)>);
+    for event_name in &["copy", "cut"] {
+        if let Err(e) = document
+            .add_event_listener_with_callback(event_name, on_clipboard.as_ref().unchecked_ref())
+        {
+            log::error!("unable to guard the password inputs: {:?}", e);
+        }
+    }
+    let on_reveal = Closure::wrap(Box::new(|event: web_sys::Event| {
+        if closest(&event, &format!("[{}]", REVEAL_MARKER)).is_none() {
+            return;
+        }
+        let password_input = closest(&event, &format!("[{}]", PASSWORD_BOX_MARKER))
+            .map(|password_box| {
+                password_box
+                    .query_selector(&format!("[{}]", PASSWORD_MARKER))
+                    .ok()
+            })
+            .flatten()
+            .flatten()
+            .map(|element| element.dyn_into::<HtmlInputElement>().ok())
+            .flatten();
+        if let Some(password_input) = password_input {
+            if password_input.type_() == "password" {
+                password_input.set_type("text");
+            } else {
+                password_input.set_type("password");
+            }
+        }
+    }) as Box<dyn FnMut(web_sys::Event)>);
+    if let Err(e) =
+        document.add_event_listener_with_callback("click", on_reveal.as_ref().unchecked_ref())
+    {
+        log::error!("unable to listen to the reveal buttons: {:?}", e);
+    }
+    // the listeners live as long as the page
+    on_clipboard.forget();
+    on_reveal.forget();
+}
+
+/// Cancel the clicks on the read only checkboxes and radios, so they are not toggled.
+/// The keys which toggle them are sent to them as clicks, so they are canceled too.
+///
+/// The listener is on the document for the same reason as the ones of guard_passwords.
+fn guard_read_only() {
+    let document = match web_sys::window().map(|window| window.document()).flatten() {
+        Some(document) => document,
+        None => return,
+    };
+    let read_only_selector = format!("[{}]", READ_ONLY_MARKER);
+    let on_click = Closure::wrap(Box::new(move |event: web_sys::Event| {
+        let is_read_only = event
+            .target()
+            .map(|target| target.dyn_into::<web_sys::Element>().ok())
+            .flatten()
+            .map(|element| element.matches(&read_only_selector).ok())
+            .flatten()
+            .unwrap_or(false);
+        if is_read_only {
+            event.prevent_default();
+        }
+    }) as Box<dyn FnMut(web_sys::Event)>);
+    if let Err(e) =
+        document.add_event_listener_with_callback("click", on_click.as_ref().unchecked_ref())
+    {
+        log::error!("unable to guard the read only inputs: {:?}", e);
+    }
+    // the listener lives as long as the page
+    on_click.forget();
+}
+
+/// Send a `colorschemechange` event to the windows listening to the color scheme
+/// once they are rendered and whenever the preference changes.
+/// The windows are rendered with the theme of the new color scheme in the next view.
+fn watch_color_scheme() {
+    fn notify_windows() {
+        let windows = web_sys::window()
+            .map(|window| window.document())
+            .flatten()
+            .map(|document| document.query_selector_all("[data-color-scheme]").ok())
+            .flatten();
+        if let Some(windows) = windows {
+            for i in 0..windows.length() {
+                if let Some(window) = windows.get(i) {
+                    if let Ok(event) = web_sys::Event::new(COLOR_SCHEME_EVENT) {
+                        if let Err(e) = window.dispatch_event(&event) {
+                            log::error!("unable to dispatch the color scheme: {:?}", e);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    let window = match web_sys::window() {
+        Some(window) => window,
+        None => return,
+    };
+    let notify = Closure::once_into_js(notify_windows);
+    if let Err(e) = window.set_timeout_with_callback(notify.unchecked_ref()) {
+        log::error!("unable to schedule the color scheme: {:?}", e);
+    }
+    if let Ok(Some(media)) = window.match_media("(prefers-color-scheme: dark)") {
+        let on_change =
+            Closure::wrap(Box::new(|_event: web_sys::Event| notify_windows())
+                as Box<dyn FnMut(web_sys::Event)>);
+        if let Err(e) =
+            media.add_event_listener_with_callback("change", on_change.as_ref().unchecked_ref())
+        {
+            log::error!("unable to watch the color scheme: {:?}", e);
+        }
+        // the listener lives as long as the page
+        on_change.forget();
+    }
+}
+
+/// emit the color scheme to a window, the window is marked so watch_color_scheme finds it
+fn on_color_scheme<MSG>(cb: Callback<sauron_vdom::Event, MSG>) -> Vec<sauron::Attribute<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+{
+    vec![
+        attr("data-color-scheme", "true"),
+        events::on(COLOR_SCHEME_EVENT, move |_event: sauron::Event| {
+            cb.emit(InputEvent::new(
+                preferred_color_scheme().as_str().to_string(),
+            ))
+        }),
+    ]
+}
+
+fn set_document_title(title: &str) {
+    if let Some(document) = web_sys::window().map(|window| window.document()).flatten() {
+        if document.title() != title {
+            document.set_title(title);
+        }
+    }
+}
+
+/// point the favicon of the document to the icon, adding the link element if there is none
+fn set_document_icon(icon: &str) -> Option<()> {
+    let document = web_sys::window()?.document()?;
+    let link = match document.query_selector("link[rel~='icon']").ok()? {
+        Some(link) => link,
+        None => {
+            let link = document.create_element("link").ok()?;
+            link.set_attribute("rel", "icon").ok()?;
+            document
+                .query_selector("head")
+                .ok()??
+                .append_child(&link)
+                .ok()?;
+            link
+        }
+    };
+    if link.get_attribute("href").as_deref() != Some(icon) {
+        link.set_attribute("href", icon).ok()?;
+    }
+    Some(())
+}
+
+/// emit the new checked state of a checkbox like input as an InputEvent
+/// with the value "true" or "false"
+fn on_checked_change<MSG>(cb: Callback<sauron_vdom::Event, MSG>) -> sauron::Attribute<MSG>
+where
+    MSG: Clone + Debug + 'static,
+{
+    events::on("change", move |event: sauron::Event| {
+        let checked = event
+            .target()
+            .map(|target| target.unchecked_into::<HtmlInputElement>().checked())
+            .unwrap_or(false);
+        cb.emit(InputEvent::new(checked.to_string()))
+    })
+}
+
+/// the change event of date and time inputs is only emitted once the value is complete
+fn on_value_change<MSG>(cb: Callback<sauron_vdom::Event, MSG>) -> sauron::Attribute<MSG>
+where
+    MSG: Clone + Debug + 'static,
+{
+    events::on("change", move |event: sauron::Event| {
+        let value = event
+            .target()
+            .map(|target| target.unchecked_into::<HtmlInputElement>().value())
+            .unwrap_or_default();
+        cb.emit(InputEvent::new(value))
+    })
+}
+
+/// Tool items show the icon when it is a path or url to an image and the label.
+/// Icon names from a desktop icon theme can not be shown in the browser.
+fn tool_item_content<MSG>(attrs: &Vec<Attribute<MSG>>) -> Vec<sauron::Node<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let mut content = vec![];
+    if let Some(icon) = find_value(AttribKey::Icon, attrs).map(|v| v.to_string()) {
+        if icon.contains('/') || icon.contains('.') {
+            content.push(img(
+                vec![src(icon), styles([("width", "16px"), ("height", "16px")])],
                 vec![],
-            )
+            ));
         }
-        Widget::Svg => {
-            let empty = vec![];
-            let bytes = find_value(AttribKey::Data, &attrs)
-                .map(|v| v.as_bytes())
-                .flatten()
-                .unwrap_or(&empty);
-            img(
-                vec![
-                    styles([
-                        ("width", "100%"),
-                        ("height", "auto"),
-                        ("max-width", "800px"),
-                    ]),
-                    src(format!(
-                        "data:image/svg+xml;base64,{}",
-                        base64::encode(bytes)
-                    )),
-                ],
+    }
+    if let Some(label) = find_value(AttribKey::Label, attrs).map(|v| v.to_string()) {
+        content.push(text(label));
+    }
+    content
+}
+
+fn tool_item_styles(pressed: bool) -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("display", "flex"),
+        ("align-items", "center"),
+        ("gap", "4px"),
+        ("padding", "4px 8px"),
+        ("border", "none"),
+        ("border-radius", "3px"),
+        ("cursor", "pointer"),
+        (
+            "background",
+            if pressed { "#d0d0d0" } else { "transparent" },
+        ),
+    ]
+}
+
+fn on_details_toggle<MSG>(cb: Callback<sauron_vdom::Event, MSG>) -> sauron::Attribute<MSG>
+where
+    MSG: Clone + Debug + 'static,
+{
+    events::on("toggle", move |event: sauron::Event| {
+        let open = event
+            .target()
+            .map(|target| target.unchecked_into::<HtmlDetailsElement>().open())
+            .unwrap_or(false);
+        cb.emit(InputEvent::new(open.to_string()))
+    })
+}
+
+/// a link with a click listener emits it instead of following its uri
+fn on_link_click<MSG>(cb: Callback<sauron_vdom::Event, MSG>) -> sauron::Attribute<MSG>
+where
+    MSG: Clone + Debug + 'static,
+{
+    events::on("click", move |event: sauron::Event| {
+        event.prevent_default();
+        cb.emit(counted_mouse_event(event))
+    })
+}
+
+/// the click listener of a button, with the click count of the click
+fn on_click<MSG>(cb: Callback<sauron_vdom::Event, MSG>) -> sauron::Attribute<MSG>
+where
+    MSG: Clone + Debug + 'static,
+{
+    events::on("click", move |event: sauron::Event| {
+        cb.emit(counted_mouse_event(event))
+    })
+}
+
+/// the styles a child needs to be placed inside its parent widget,
+/// index is the position of the child among its siblings
+fn child_layout_attributes<MSG>(
+    parent: &Widget,
+    parent_attrs: &Vec<Attribute<MSG>>,
+    index: usize,
+    child_attrs: &Vec<Attribute<MSG>>,
+) -> Vec<sauron::Attribute<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+{
+    match parent {
+        Widget::Grid => {
+            let cell = GridCell::from_attrs(child_attrs);
+            // css grid lines starts at 1
+            vec![styles([
+                (
+                    "grid-row",
+                    format!("{} / span {}", cell.row + 1, cell.row_span),
+                ),
+                (
+                    "grid-column",
+                    format!("{} / span {}", cell.column + 1, cell.column_span),
+                ),
+            ])]
+        }
+        // cascade the windows so the title bars don't cover each other
+        Widget::Application => {
+            let key = find_value(AttribKey::Key, child_attrs)
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| index.to_string());
+            let offset = format!("{}px", 20 + index * 30);
+            vec![
+                attr("key", key),
+                styles([("top", offset.clone()), ("left", offset)]),
+            ]
+        }
+        Widget::Stack if index > 0 => {
+            let halign = Align::find(AttribKey::HorizontalAlign, child_attrs);
+            let valign = Align::find(AttribKey::VerticalAlign, child_attrs);
+            let (left, right, translate_x) = overlay_position(halign);
+            let (top, bottom, translate_y) = overlay_position(valign);
+            vec![styles([
+                ("position", "absolute".to_string()),
+                ("left", left.to_string()),
+                ("right", right.to_string()),
+                ("top", top.to_string()),
+                ("bottom", bottom.to_string()),
+                (
+                    "transform",
+                    format!("translate({}, {})", translate_x, translate_y),
+                ),
+            ])]
+        }
+        Widget::Pages => {
+            let is_visible =
+                layout::page_name(index, child_attrs) == layout::visible_page(parent_attrs);
+            let transition = Transition::find(parent_attrs);
+            let hidden_transform = match transition {
+                Transition::SlideLeft => "translateX(-100%)",
+                Transition::SlideRight => "translateX(100%)",
+                Transition::SlideUp => "translateY(-100%)",
+                Transition::SlideDown => "translateY(100%)",
+                Transition::None | Transition::Crossfade => "none",
+            };
+            let animation = match transition {
+                Transition::None => "none",
+                Transition::Crossfade => "opacity 250ms, visibility 250ms",
+                _ => "transform 250ms, visibility 250ms",
+            };
+            vec![styles([
+                ("grid-area", "1 / 1"),
+                ("visibility", if is_visible { "visible" } else { "hidden" }),
+                ("opacity", if is_visible { "1" } else { "0" }),
+                (
+                    "transform",
+                    if is_visible { "none" } else { hidden_transform },
+                ),
+                ("transition", animation),
+            ])]
+        }
+        // the widgets laying out their children in a flex line
+        Widget::Window
+        | Widget::Vbox
+        | Widget::Hbox
+        | Widget::Vpane
+        | Widget::Hpane
+        | Widget::Toolbar
+        | Widget::StatusBar
+            if Flex::is_declared(child_attrs) =>
+        {
+            let flex = Flex::from_attrs(child_attrs);
+            let is_row = match parent {
+                Widget::Hbox | Widget::Hpane | Widget::Toolbar | Widget::StatusBar => true,
+                _ => false,
+            };
+            let mut flex_styles = vec![("flex-shrink", flex.shrink.to_string())];
+            // auto margins take the space left instead of the child
+            if flex.fill || !flex.expands() {
+                flex_styles.push(("flex-grow", flex.grow.to_string()));
+            } else if is_row {
+                flex_styles.push(("margin-left", "auto".to_string()));
+                flex_styles.push(("margin-right", "auto".to_string()));
+            } else {
+                flex_styles.push(("margin-top", "auto".to_string()));
+                flex_styles.push(("margin-bottom", "auto".to_string()));
+            }
+            vec![styles(flex_styles)]
+        }
+        _ => vec![],
+    }
+}
+
+/// the minimum and maximum size of the widget
+fn size_constraint_attributes<MSG>(attrs: &Vec<Attribute<MSG>>) -> Vec<sauron::Attribute<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let constraints = SizeConstraints::from_attrs(attrs);
+    if constraints.is_empty() {
+        return vec![];
+    }
+    let size_styles: Vec<(&str, String)> = vec![
+        ("min-width", constraints.min_width),
+        ("min-height", constraints.min_height),
+        ("max-width", constraints.max_width),
+        ("max-height", constraints.max_height),
+    ]
+    .into_iter()
+    .filter_map(|(property, size)| size.map(|size| (property, format!("{}px", size))))
+    .collect();
+    vec![styles(size_styles)]
+}
+
+/// a hidden widget stays in the document so it keeps its state, it is taken out
+/// of the layout instead. It comes after the other styles, so it overrides their display
+fn visibility_attributes<MSG>(attrs: &Vec<Attribute<MSG>>) -> Vec<sauron::Attribute<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+{
+    if state::is_visible(attrs) {
+        vec![]
+    } else {
+        vec![styles([("display", "none")])]
+    }
+}
+
+/// the windows of an application are shown as panels floating over the page,
+/// with a title bar and a close button when the window listens to close events
+fn floating_window<MSG>(
+    content: sauron::Node<MSG>,
+    window_attrs: &Vec<Attribute<MSG>>,
+) -> sauron::Node<MSG>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let config = WindowConfig::from_attrs(window_attrs);
+    let close_button = find_callback(AttribKey::CloseEvent, window_attrs)
+        .cloned()
+        .map(|cb| {
+            input(
+                vec![r#type("button"), value("×"), onclick(move |ev| cb.emit(ev))],
                 vec![],
             )
-        }
+        });
+    let title_bar = div(
+        vec![styles([
+            ("display", "flex"),
+            ("justify-content", "space-between"),
+            ("padding", "2px 6px"),
+            ("background", "#dddddd"),
+        ])],
+        std::iter::once(text(config.title.unwrap_or(String::new())))
+            .chain(close_button)
+            .collect(),
+    );
+    let mut panel_styles = vec![
+        ("position", "absolute".to_string()),
+        ("background", "white".to_string()),
+        ("border", "1px solid #888888".to_string()),
+        ("box-shadow", "2px 2px 8px rgba(0, 0, 0, 0.3)".to_string()),
+        ("overflow", "auto".to_string()),
+    ];
+    if config.resizable.unwrap_or(true) {
+        panel_styles.push(("resize", "both".to_string()));
+    }
+    if let Some(width) = config.width {
+        panel_styles.push(("width", format!("{}px", width)));
+    }
+    if let Some(height) = config.height {
+        panel_styles.push(("height", format!("{}px", height)));
+    }
+    div(vec![styles(panel_styles)], vec![title_bar, content])
+}
+
+/// the inset at the start, the inset at the end and the translation of an absolutely
+/// positioned child along one axis
+fn overlay_position(align: Align) -> (&'static str, &'static str, &'static str) {
+    match align {
+        Align::Fill => ("0", "0", "0"),
+        Align::Start => ("0", "auto", "0"),
+        Align::Center => ("50%", "auto", "-50%"),
+        Align::End => ("auto", "0", "0"),
+    }
+}
+
+/// the style the widget gets from the theme and from its style attribute,
+/// the color and the font are inherited by the html children
+fn style_attributes<MSG>(
+    theme: &Theme,
+    widget: &Widget,
+    attrs: &Vec<Attribute<MSG>>,
+) -> Vec<sauron::Attribute<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let style = theme
+        .style_for(widget)
+        .merge(&Style::find(attrs).unwrap_or_default());
+    if style.is_empty() {
+        return vec![];
     }
+    vec![styles(style.declarations())]
 }
 
 /// converts widget virtual node tree into an html node tree
 pub fn widget_tree_to_html_node<MSG>(widget_node: crate::Node<MSG>) -> sauron::Node<MSG>
+where
+    MSG: Clone + Debug + 'static,
+{
+    themed_tree_to_html_node(widget_node, &Theme::default(), &[])
+}
+
+/// the widgets are styled with the theme of the window they are in
+fn themed_tree_to_html_node<MSG>(
+    widget_node: crate::Node<MSG>,
+    theme: &Theme,
+    path: &[usize],
+) -> sauron::Node<MSG>
 where
     MSG: Clone + Debug + 'static,
 {
     match widget_node {
         crate::Node::Element(widget) => {
+            let window_theme = match widget.tag {
+                Widget::Window => Theme::for_color_scheme(&widget.attrs, preferred_color_scheme()),
+                _ => None,
+            };
+            let theme = window_theme.as_ref().unwrap_or(theme);
+            // the placement of the children depends on the attributes of this widget,
+            // which is consumed when converting it
+            let children_attrs: Vec<Vec<Attribute<MSG>>> = widget
+                .children
+                .iter()
+                .map(|child| child.get_attributes())
+                .collect();
+            let child_layouts: Vec<Vec<sauron::Attribute<MSG>>> = children_attrs
+                .iter()
+                .enumerate()
+                .map(|(index, child_attrs)| {
+                    child_layout_attributes(&widget.tag, &widget.attrs, index, child_attrs)
+                })
+                .collect();
+            let focus_attributes = focus_attributes(&widget.attrs);
+            let size_constraints = size_constraint_attributes(&widget.attrs);
+            let style = style_attributes(theme, &widget.tag, &widget.attrs);
+            let visibility = visibility_attributes(&widget.attrs);
+            let key_listeners = key_attributes(&widget.attrs);
+            let mouse_listeners = mouse_attributes(&widget.attrs);
+            let focus_listeners = focus_listener_attributes(&widget.attrs);
             // convert the Widget tag to html node
-            let mut html_node: sauron::Node<MSG> = widget_to_html(&widget.tag, widget.attrs);
+            let mut html_node: sauron::Node<MSG> = widget_to_html(&widget.tag, widget.attrs, path)
+                .add_attributes(focus_attributes)
+                .add_attributes(size_constraints)
+                .add_attributes(style)
+                .add_attributes(visibility)
+                .add_attributes(key_listeners)
+                .add_attributes(mouse_listeners)
+                .add_attributes(focus_listeners);
             // cast the html node to element
             if let Some(html_element) = html_node.as_element_mut() {
-                for widget_child in widget.children {
+                for (index, ((widget_child, child_attrs), child_layout)) in widget
+                    .children
+                    .into_iter()
+                    .zip(children_attrs)
+                    .zip(child_layouts)
+                    .enumerate()
+                {
+                    let child_path = [path, &[index]].concat();
                     // convert all widget child to an html child node
-                    let mut html_child: sauron::Node<MSG> = widget_tree_to_html_node(widget_child);
+                    let mut html_child: sauron::Node<MSG> =
+                        themed_tree_to_html_node(widget_child, theme, &child_path);
+                    if widget.tag == Widget::Application {
+                        html_child = floating_window(html_child, &child_attrs);
+                    }
+                    html_child = html_child.add_attributes(child_layout);
                     html_element.children.push(html_child);
                 }
             }
@@ -1,17 +1,24 @@
 use super::Dispatch;
 use crate::{
-    widget::attribute::{find_callback, find_value},
+    util,
+    widget::{
+        attribute::{find_callback, find_value},
+        layout::{Fit, Flex, SizeConstraints},
+        state,
+        window::{self, WindowConfig},
+    },
     AttribKey, Attribute, Backend, Component, Node, Patch, Widget,
 };
-use image::{bmp::BMPEncoder, ColorType, GenericImageView, ImageEncoder};
+use image::{bmp::BMPEncoder, ColorType, DynamicImage, GenericImageView, ImageEncoder};
 use native_windows_gui as nwg;
 use nwg::{
     stretch::{
         geometry::Size,
         style::{Dimension, FlexDirection},
     },
-    Bitmap, Button, CheckBox, ControlHandle, FlexboxLayout, ImageDecoder, ImageFrame, Label,
-    RadioButton, RichTextBox, TextBox, TextInput, Window,
+    Bitmap, Button, CheckBox, CheckBoxState, ControlHandle, FlexboxLayout, FlexboxLayoutBuilder,
+    ImageDecoder, ImageFrame, Label, RadioButton, RadioButtonState, RichTextBox, TextBox,
+    TextInput, Window,
 };
 
 use std::{cell::RefCell, fmt, fmt::Debug, marker::PhantomData, rc::Rc};
@@ -36,16 +43,28 @@ impl<APP, MSG> NwgBackend<APP, MSG> {
         println!("1 new");
         nwg::init().expect("Failed to init Native Windows GUI");
         let current_vdom = app.view();
-        let root_vdom = app.view();
+        //TODO: open a window for each window of an application
+        let root_vdom = window::window_nodes(&current_vdom)
+            .first()
+            .map(|(_key, window)| (*window).clone())
+            .expect("must have a window");
+        let config = WindowConfig::from_root(&root_vdom).unwrap_or_default();
+
+        let mut flags = nwg::WindowFlags::WINDOW | nwg::WindowFlags::VISIBLE;
+        if config.resizable.unwrap_or(true) {
+            flags |= nwg::WindowFlags::RESIZABLE;
+        }
+        let title = config.title.unwrap_or("Windows Backend".to_string());
 
         let mut window: Window = Window::default();
         Window::builder()
-            .flags(
-                nwg::WindowFlags::WINDOW | nwg::WindowFlags::VISIBLE | nwg::WindowFlags::RESIZABLE,
-            )
-            .size((800, 800))
+            .flags(flags)
+            .size((
+                config.width.map(|w| w as i32).unwrap_or(800),
+                config.height.map(|h| h as i32).unwrap_or(800),
+            ))
             .position((300, 300))
-            .title("Windows Backend")
+            .title(&title)
             .build(&mut window)
             .unwrap();
 
@@ -141,6 +160,59 @@ impl fmt::Debug for NwgWidget {
     }
 }
 
+/// a child stretched over the width of its column
+fn full_width(height: f32) -> Size<Dimension> {
+    Size {
+        width: Dimension::Percent(1.0),
+        height: Dimension::Points(height),
+    }
+}
+
+/// Lay out the child added last to the builder from its size and flex attributes.
+/// A growing child without a declared height starts from no height, so it takes
+/// the space left in the column instead of its default height.
+fn flex_child<MSG>(
+    builder: FlexboxLayoutBuilder,
+    attrs: &Vec<Attribute<MSG>>,
+    default_size: Size<Dimension>,
+) -> FlexboxLayoutBuilder
+where
+    MSG: 'static,
+{
+    let flex = Flex::from_attrs(attrs);
+    let constraints = SizeConstraints::from_attrs(attrs);
+    let points = |key| {
+        find_value(key, attrs)
+            .map(|v| v.as_f64())
+            .flatten()
+            .map(|v| Dimension::Points(v as f32))
+    };
+    let size = Size {
+        width: points(AttribKey::Width).unwrap_or(default_size.width),
+        height: points(AttribKey::Height).unwrap_or(if flex.expands() {
+            Dimension::Auto
+        } else {
+            default_size.height
+        }),
+    };
+    let constraint = |size: Option<f64>| {
+        size.map(|v| Dimension::Points(v as f32))
+            .unwrap_or(Dimension::Undefined)
+    };
+    builder
+        .child_size(size)
+        .child_flex_grow(flex.grow as f32)
+        .child_flex_shrink(flex.shrink as f32)
+        .child_min_size(Size {
+            width: constraint(constraints.min_width),
+            height: constraint(constraints.min_height),
+        })
+        .child_max_size(Size {
+            width: constraint(constraints.max_width),
+            height: constraint(constraints.max_height),
+        })
+}
+
 impl NwgWidget {
     fn as_box(self) -> Option<FlexboxLayout> {
         match self {
@@ -162,6 +234,12 @@ impl NwgWidget {
         match widget_node {
             crate::Node::Element(element) => {
                 println!("element...");
+                // the flex attributes of the children are needed to lay them out in the parent
+                let children_attrs: Vec<Vec<Attribute<MSG>>> = element
+                    .children
+                    .iter()
+                    .map(|child| child.get_attributes())
+                    .collect();
                 let mut children: Vec<(Self, Vec<Self>)> = element
                     .children
                     .into_iter()
@@ -170,8 +248,14 @@ impl NwgWidget {
 
                 let mut all_children = vec![];
                 let (direct, indirect): (Vec<Self>, Vec<Vec<Self>>) = children.into_iter().unzip();
-                let nwg_widget =
-                    Self::from_node(window, program, element.tag, &direct, element.attrs);
+                let nwg_widget = Self::from_node(
+                    window,
+                    program,
+                    element.tag,
+                    &direct,
+                    &children_attrs,
+                    element.attrs,
+                );
                 all_children.extend(direct);
                 all_children.extend(indirect.into_iter().flatten());
                 (nwg_widget, all_children)
@@ -185,6 +269,7 @@ impl NwgWidget {
         program: &DSP,
         widget: Widget,
         children: &Vec<Self>,
+        children_attrs: &Vec<Vec<Attribute<MSG>>>,
         attrs: Vec<Attribute<MSG>>,
     ) -> Self
     where
@@ -193,7 +278,16 @@ impl NwgWidget {
     {
         println!("from node...");
         match widget {
-            Widget::Vbox => {
+            //TODO: place the children of the grid in their declared cells,
+            // layer the children of a stack, show only the visible page
+            // and make the expander collapsible
+            Widget::Application
+            | Widget::Window
+            | Widget::Vbox
+            | Widget::Grid
+            | Widget::Stack
+            | Widget::Pages
+            | Widget::Expander => {
                 println!("vbox..");
                 let mut box_layout = FlexboxLayout::default();
 
@@ -201,8 +295,9 @@ impl NwgWidget {
                     .parent(window)
                     .flex_direction(FlexDirection::Column);
 
-                for child in children.iter() {
-                    match child {
+                for (child, child_attrs) in children.iter().zip(children_attrs) {
+                    // the default size of the child, used for the sides its attributes don't declare
+                    let (handle, default_size): (ControlHandle, Size<Dimension>) = match child {
                         NwgWidget::Box(child) => {
                             /*
                             box_layout.add_child(child, Style{
@@ -211,57 +306,30 @@ impl NwgWidget {
                                     height: Dimension::Percent(1.0),
                                 }
                             });*/
+                            continue;
                         }
-                        NwgWidget::Button(child) => {
-                            builder = builder.child(child).child_size(Size {
+                        NwgWidget::Button(child) => (
+                            child.into(),
+                            Size {
                                 width: Dimension::Points(20.0),
                                 height: Dimension::Points(20.0),
-                            });
-                        }
-                        NwgWidget::Paragraph(child) => {
-                            builder = builder.child(child).child_size(Size {
-                                width: Dimension::Percent(1.0),
-                                height: Dimension::Points(20.0),
-                            });
-                        }
-                        NwgWidget::TextInput(child) => {
-                            builder = builder.child(child).child_size(Size {
-                                width: Dimension::Percent(1.0),
-                                height: Dimension::Points(20.0),
-                            });
-                        }
-                        NwgWidget::TextArea(child) => {
-                            builder = builder.child(child).child_size(Size {
-                                width: Dimension::Percent(1.0),
-                                height: Dimension::Points(20.0),
-                            });
-                        }
-                        NwgWidget::Checkbox(child) => {
-                            builder = builder.child(child).child_size(Size {
-                                width: Dimension::Percent(1.0),
-                                height: Dimension::Points(20.0),
-                            });
-                        }
-                        NwgWidget::Radio(child) => {
-                            builder = builder.child(child).child_size(Size {
-                                width: Dimension::Percent(1.0),
-                                height: Dimension::Points(20.0),
-                            });
-                        }
-                        NwgWidget::Image(child, _) => {
-                            builder = builder.child(child).child_size(Size {
-                                width: Dimension::Percent(1.0),
-                                height: Dimension::Points(400.0),
-                            });
-                        }
-                    }
+                            },
+                        ),
+                        NwgWidget::Paragraph(child) => (child.into(), full_width(20.0)),
+                        NwgWidget::TextInput(child) => (child.into(), full_width(20.0)),
+                        NwgWidget::TextArea(child) => (child.into(), full_width(20.0)),
+                        NwgWidget::Checkbox(child) => (child.into(), full_width(20.0)),
+                        NwgWidget::Radio(child) => (child.into(), full_width(20.0)),
+                        NwgWidget::Image(child, _) => (child.into(), full_width(400.0)),
+                    };
+                    builder = flex_child(builder.child(handle), child_attrs, default_size);
                 }
 
                 builder.build(&mut box_layout);
 
                 NwgWidget::Box(box_layout)
             }
-            Widget::Hbox => {
+            Widget::Hbox | Widget::Toolbar | Widget::StatusBar => {
                 println!("hbox..");
                 let mut box_layout = FlexboxLayout::default();
 
@@ -297,7 +365,7 @@ impl NwgWidget {
 
                 NwgWidget::Box(box_layout)
             }
-            Widget::Button => {
+            Widget::Button | Widget::ToolButton | Widget::Link => {
                 println!("button..");
                 let label = find_value(AttribKey::Value, &attrs)
                     .map(|v| v.to_string())
@@ -311,10 +379,16 @@ impl NwgWidget {
                     .parent(window)
                     .build(&mut btn)
                     .expect("must build button");
+                btn.set_enabled(!state::is_disabled(&attrs));
 
                 NwgWidget::Button(btn)
             }
-            Widget::Paragraph => {
+            // the separators and spaces are an empty text
+            Widget::Paragraph
+            | Widget::StatusSegment
+            | Widget::ToolSeparator
+            | Widget::Separator
+            | Widget::Space => {
                 let txt = find_value(AttribKey::Value, &attrs)
                     .map(|v| v.to_string())
                     .unwrap_or(String::new());
@@ -329,20 +403,29 @@ impl NwgWidget {
 
                 NwgWidget::Paragraph(rtb)
             }
-            Widget::TextInput => {
+            // TODO: use nwg::DatePicker, the pickers are text inputs holding the ISO value for now
+            Widget::TextInput | Widget::DatePicker | Widget::TimePicker => {
                 println!("textinput..");
                 let value = find_value(AttribKey::Value, &attrs)
                     .map(|v| v.to_string())
                     .unwrap_or(String::new());
 
+                let password = find_value(AttribKey::Password, &attrs)
+                    .map(|v| v.as_bool())
+                    .flatten()
+                    .unwrap_or(false);
+
                 let mut text_input = TextInput::default();
 
                 TextInput::builder()
                     .size((280, 20))
                     .text(&value)
+                    .password(if password { Some('*') } else { None })
                     .parent(window)
                     .build(&mut text_input)
                     .expect("must build text input");
+                text_input.set_enabled(!state::is_disabled(&attrs));
+                text_input.set_readonly(state::is_read_only(&attrs));
 
                 NwgWidget::TextInput(text_input)
             }
@@ -360,20 +443,21 @@ impl NwgWidget {
                     .parent(window)
                     .build(&mut text_box)
                     .expect("must build textbox");
+                text_box.set_enabled(!state::is_disabled(&attrs));
+                text_box.set_readonly(state::is_read_only(&attrs));
 
                 NwgWidget::TextArea(text_box)
             }
 
-            Widget::Checkbox => {
+            // nwg has no native switch or toggle button control,
+            // a checkbox carries the same state
+            Widget::Checkbox | Widget::Switch | Widget::ToolToggle => {
                 println!("checkbox..");
                 let label = find_value(AttribKey::Label, &attrs)
                     .map(|v| v.to_string())
                     .unwrap_or(String::new());
 
-                let value = find_value(AttribKey::Value, &attrs)
-                    .map(|v| v.as_bool())
-                    .flatten()
-                    .unwrap_or(false);
+                let checked = state::is_checked(&attrs);
 
                 let mut checkbox = CheckBox::default();
                 CheckBox::builder()
@@ -382,6 +466,10 @@ impl NwgWidget {
                     .parent(window)
                     .build(&mut checkbox)
                     .expect("must build checkbox");
+                checkbox.set_enabled(!state::is_disabled(&attrs));
+                if checked {
+                    checkbox.set_check_state(CheckBoxState::Checked);
+                }
 
                 NwgWidget::Checkbox(checkbox)
             }
@@ -391,10 +479,7 @@ impl NwgWidget {
                     .map(|v| v.to_string())
                     .unwrap_or(String::new());
 
-                let value = find_value(AttribKey::Value, &attrs)
-                    .map(|v| v.as_bool())
-                    .flatten()
-                    .unwrap_or(false);
+                let checked = state::is_checked(&attrs);
 
                 let mut radio = RadioButton::default();
                 RadioButton::builder()
@@ -403,24 +488,42 @@ impl NwgWidget {
                     .parent(window)
                     .build(&mut radio)
                     .expect("must build checkbox");
+                radio.set_enabled(!state::is_disabled(&attrs));
+                if checked {
+                    radio.set_check_state(RadioButtonState::Checked);
+                }
 
                 NwgWidget::Radio(radio)
             }
             Widget::Image => {
                 let empty = vec![];
-                let blob = find_value(AttribKey::Data, &attrs)
-                    .map(|v| v.as_bytes())
-                    .flatten()
-                    .unwrap_or(&empty);
-
-                let img = image::load_from_memory(&blob).expect("should load");
-                let (width, height) = img.dimensions();
+                let source = find_value(AttribKey::Source, &attrs)
+                    .map(|v| util::source_bytes(&v.to_string()))
+                    .flatten();
+                let blob: &[u8] = match &source {
+                    Some(bytes) => bytes,
+                    None => find_value(AttribKey::Data, &attrs)
+                        .map(|v| v.as_bytes())
+                        .flatten()
+                        .unwrap_or(&empty),
+                };
+
+                let img = util::decode_image(&blob).unwrap_or_else(|| DynamicImage::new_rgb8(1, 1));
+                let box_width = find_value(AttribKey::Width, &attrs)
+                    .map(|v| v.as_f64())
+                    .flatten();
+                let box_height = find_value(AttribKey::Height, &attrs)
+                    .map(|v| v.as_f64())
+                    .flatten();
+                let (width, height) = util::box_size(box_width, box_height, img.dimensions());
+                let fitted = util::fit_image(&img, Fit::find(&attrs), (width, height));
+                let (fitted_width, fitted_height) = fitted.dimensions();
                 let mut bytes: Vec<u8> = vec![];
 
                 BMPEncoder::new(&mut bytes).write_image(
-                    &img.to_rgb().into_raw(),
-                    width,
-                    height,
+                    &fitted.to_rgb().into_raw(),
+                    fitted_width,
+                    fitted_height,
                     ColorType::Rgb8,
                 );
 
@@ -441,10 +544,16 @@ impl NwgWidget {
             }
             Widget::Svg => {
                 let empty = vec![];
-                let bytes = find_value(AttribKey::Data, &attrs)
-                    .map(|v| v.as_bytes())
-                    .flatten()
-                    .unwrap_or(&empty);
+                let source = find_value(AttribKey::Source, &attrs)
+                    .map(|v| util::source_bytes(&v.to_string()))
+                    .flatten();
+                let bytes: &[u8] = match &source {
+                    Some(bytes) => bytes,
+                    None => find_value(AttribKey::Data, &attrs)
+                        .map(|v| v.as_bytes())
+                        .flatten()
+                        .unwrap_or(&empty),
+                };
                 let rtree = resvg::usvg::Tree::from_data(&bytes, &resvg::usvg::Options::default())
                     .expect("must be parse into tree");
                 let svg_size = rtree.svg_node().size;
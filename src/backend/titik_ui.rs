@@ -1,16 +1,26 @@
 use crate::{
+    util,
     widget::{
         attribute::{find_callback, find_value},
+        date::{Date, Time},
+        focus::{self, FocusTarget},
+        layout::{self, Align, Alignment, Fit, Flex, GridCell, Insets, SizeConstraints},
+        state,
+        style::{ColorScheme, Style as WidgetStyle, Theme},
+        window::{self, WindowConfig},
         Widget,
     },
     AttribKey, Attribute, Backend, Component, Node,
 };
-use image::{GenericImageView, ImageBuffer, RgbaImage};
+use image::{DynamicImage, GenericImageView, ImageBuffer, ImageOutputFormat, RgbaImage};
 use std::{
-    cell::RefCell,
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap},
     fmt::Debug,
     io::{self, Stdout, Write},
     marker::PhantomData,
+    path::PathBuf,
     rc::Rc,
     sync::mpsc,
     thread,
@@ -25,17 +35,69 @@ use titik::{
     },
     find_layout, find_widget_mut, set_focused_node,
     stretch::{
-        geometry::Size,
+        geometry::{Rect, Size},
         number::Number,
-        style::{Dimension, Style},
+        style::{AlignItems, Dimension, JustifyContent, PositionType, Style},
     },
     widget_node_idx_at, Buffer, Button, Checkbox, FlexBox, Image, LayoutTree, Radio, Renderer,
     SvgImage, TextArea, TextInput, Widget as Control,
 };
 
+use date_picker::{DatePicker, TimePicker};
+use focus_listener::{FocusListener, FocusMsgs};
+use key_listener::KeyListener;
+use link::Link;
+use masked::Masked;
+use mouse_listener::{ClickListener, MouseListener, MouseListeners};
+use painted::Painted;
+use restricted::Restricted;
+use separator::Separator;
+use styled::Styled;
+use toggled::Toggled;
+
+mod date_picker;
+mod focus_listener;
+mod key_listener;
+mod link;
+mod masked;
+mod mouse_listener;
+mod painted;
+mod restricted;
+mod separator;
+mod styled;
+mod toggled;
+mod wrapper;
+
+/// the number of image pixels shown in one terminal cell,
+/// cells are about twice as tall as they are wide
+const PIXELS_PER_COLUMN: f32 = 10.0;
+const PIXELS_PER_ROW: f32 = 20.0;
+
+/// the status segments are bordered, which takes up a row above and below the text
+const STATUS_BAR_HEIGHT: f32 = 3.0;
+
+thread_local! {
+    /// the control built for the node which asks for the focus, it is found again
+    /// in the layout tree by its address once the whole tree is built
+    static FOCUSED_CONTROL: Cell<Option<*const ()>> = Cell::new(None);
+    static DECODED_IMAGES: RefCell<HashMap<PathBuf, Rc<DynamicImage>>> =
+        RefCell::new(HashMap::new());
+    /// the theme of the window being built
+    static THEME: RefCell<Theme> = RefCell::new(Theme::default());
+    /// the colors of the parent of the node being built, the terminal doesn't inherit them
+    static INHERITED_STYLE: RefCell<WidgetStyle> = RefCell::new(WidgetStyle::default());
+    /// the path of the next node to build, the indexes of it and its ancestors
+    /// among the nodes built in their parent
+    static NODE_PATH: RefCell<Vec<usize>> = RefCell::new(vec![0]);
+}
+
 pub struct TitikBackend<APP, MSG> {
     app: Rc<RefCell<APP>>,
-	renderer: Rc<RefCell<Renderer<MSG>>>,
+    renderer: Rc<RefCell<Renderer<MSG>>>,
+    /// the node which asked for the focus in the last view
+    focus_target: RefCell<Option<FocusTarget>>,
+    /// the focus and blur messages of the controls, see FocusListener
+    focus_msgs: FocusMsgs<MSG>,
     _phantom_msg: PhantomData<MSG>,
 }
 
@@ -44,17 +106,164 @@ where
     APP: Component<MSG> + 'static,
     MSG: Debug + 'static,
 {
+    /// The terminal can only show one window at a time, when the view describes
+    /// several windows the one that was added last takes over the screen.
+    fn screen(view: crate::Node<MSG>) -> crate::Node<MSG> {
+        let last_window = window::window_nodes(&view)
+            .last()
+            .map(|(_key, window)| (*window).clone());
+        last_window.unwrap_or(view)
+    }
 
-    fn from_node_tree(widget_node: crate::Node<MSG>) -> Box<dyn titik::Widget<MSG>>
+    fn from_node_tree(
+        widget_node: crate::Node<MSG>,
+        focus_msgs: &FocusMsgs<MSG>,
+    ) -> Box<dyn titik::Widget<MSG>>
     where
         MSG: Debug + 'static,
     {
         match widget_node {
-            crate::Node::Element(element) => {
+            crate::Node::Element(mut element) => {
+                let tag = element.tag.clone();
+                let path = NODE_PATH.with(|node_path| {
+                    let mut node_path = node_path.borrow_mut();
+                    let path = node_path.clone();
+                    if let Some(index) = node_path.last_mut() {
+                        *index += 1;
+                    }
+                    path
+                });
+                // the controls are rebuilt on every view, so the hidden children have no state
+                // to keep and are left out. Pages keep them since the index names a page
+                if tag != Widget::Pages {
+                    element
+                        .children
+                        .retain(|child| state::is_visible(&child.get_attributes()));
+                }
                 let mut control = Self::from_node(element.tag, &element.attrs);
-                for child in element.children {
-                    let child_widget = Self::from_node_tree(child);
-                    control.add_child(child_widget);
+                let disabled = state::is_disabled(&element.attrs);
+                // a disabled control doesn't take the focus
+                if focus::is_focused(&element.attrs) && !disabled {
+                    let address = control.as_any() as *const dyn Any as *const ();
+                    FOCUSED_CONTROL.with(|focused| focused.set(Some(address)));
+                }
+                let constraints = SizeConstraints::from_attrs(&element.attrs);
+                if !constraints.is_empty() {
+                    control = Self::constrained(control, constraints);
+                }
+                if tag == Widget::Window {
+                    if let Some(theme) =
+                        Theme::for_color_scheme(&element.attrs, terminal_color_scheme())
+                    {
+                        THEME.with(|current| current.replace(theme));
+                    }
+                }
+                let style = Self::widget_style(&tag, &element.attrs);
+                let parent_style =
+                    INHERITED_STYLE.with(|inherited| inherited.replace(style.inherited()));
+                let sibling_path =
+                    NODE_PATH.with(|node_path| node_path.replace([&path[..], &[0]].concat()));
+                if tag == Widget::Grid {
+                    for grid_row in Self::grid_rows(element.children, focus_msgs) {
+                        control.add_child(grid_row);
+                    }
+                } else if tag == Widget::Stack {
+                    for (index, child) in element.children.into_iter().enumerate() {
+                        let child_attrs = child.get_attributes();
+                        let child_widget = Self::from_node_tree(child, focus_msgs);
+                        // titik draws the children in order, so the layers are drawn over the
+                        // first child when they are taken out of the flow
+                        if index > 0 {
+                            control.add_child(Self::layered(child_widget, &child_attrs));
+                        } else {
+                            control.add_child(child_widget);
+                        }
+                    }
+                } else if tag == Widget::Pages {
+                    let visible_page = layout::visible_page(&element.attrs);
+                    // only the visible page is laid out, transitions are not animated in the terminal
+                    for (index, child) in element.children.into_iter().enumerate() {
+                        let child_attrs = child.get_attributes();
+                        if layout::page_name(index, &child_attrs) == visible_page
+                            && state::is_visible(&child_attrs)
+                        {
+                            control.add_child(Self::from_node_tree(child, focus_msgs));
+                        }
+                    }
+                } else if tag == Widget::Expander {
+                    control.add_child(Self::expander_header(&element.attrs));
+                    // the children of a collapsed expander are left out of the layout
+                    let expanded = find_value(AttribKey::Expanded, &element.attrs)
+                        .map(|v| v.as_bool())
+                        .flatten()
+                        .unwrap_or(false);
+                    if expanded {
+                        for child in element.children {
+                            control.add_child(Self::from_node_tree(child, focus_msgs));
+                        }
+                    }
+                } else if tag == Widget::Vbox || tag == Widget::Hbox {
+                    let spacing = layout::spacing(&element.attrs);
+                    for (index, child) in element.children.into_iter().enumerate() {
+                        let child_attrs = child.get_attributes();
+                        let mut child_widget = Self::from_node_tree(child, focus_msgs);
+                        if Flex::is_declared(&child_attrs) {
+                            child_widget = Self::flex_item(child_widget, &tag, &child_attrs);
+                        }
+                        if index > 0 && spacing > 0.0 {
+                            child_widget = Self::spaced(child_widget, &tag, spacing);
+                        }
+                        control.add_child(child_widget);
+                    }
+                } else {
+                    for child in element.children {
+                        let child_attrs = child.get_attributes();
+                        let mut child_widget = Self::from_node_tree(child, focus_msgs);
+                        if Flex::is_declared(&child_attrs) {
+                            child_widget = Self::flex_item(child_widget, &tag, &child_attrs);
+                        }
+                        control.add_child(child_widget);
+                    }
+                }
+                INHERITED_STYLE.with(|inherited| inherited.replace(parent_style));
+                NODE_PATH.with(|node_path| node_path.replace(sibling_path));
+                if !style.is_empty() {
+                    control = Box::new(Painted::new(control, style));
+                }
+                // wrapped after the painting, so the dimmed cells are not painted over,
+                // and before the listeners, so a read only control still emits them.
+                // A disabled control takes no events, its listeners are left out
+                if disabled {
+                    return Box::new(Restricted::disabled(control));
+                } else if state::is_read_only(&element.attrs) {
+                    let toggles =
+                        tag == Widget::Checkbox || tag == Widget::Radio || tag == Widget::Switch;
+                    control = Box::new(Restricted::read_only(control, toggles));
+                }
+                if let Some(cb) = find_callback(AttribKey::KeyDownEvent, &element.attrs) {
+                    control = Box::new(KeyListener::new(control, vec![cb.clone()]));
+                }
+                let mouse_listeners = MouseListeners {
+                    on_mouse_down: find_callback(AttribKey::MouseDownEvent, &element.attrs)
+                        .cloned(),
+                    on_mouse_up: find_callback(AttribKey::MouseUpEvent, &element.attrs).cloned(),
+                    on_mouse_move: find_callback(AttribKey::MouseMoveEvent, &element.attrs)
+                        .cloned(),
+                    on_double_click: find_callback(AttribKey::DoubleClickEvent, &element.attrs)
+                        .cloned(),
+                };
+                if !mouse_listeners.is_empty() {
+                    control = Box::new(MouseListener::new(control, mouse_listeners));
+                }
+                let on_focus = find_callback(AttribKey::FocusEvent, &element.attrs).cloned();
+                let on_blur = find_callback(AttribKey::BlurEvent, &element.attrs).cloned();
+                // the interactive controls are wrapped even without listeners,
+                // the events they receive carry the pending focus and blur messages
+                if is_interactive(&tag) || on_focus.is_some() || on_blur.is_some() {
+                    let value = focus_value(&tag, &element.attrs);
+                    control = Box::new(FocusListener::new(
+                        control, path, value, on_focus, on_blur, focus_msgs,
+                    ));
                 }
                 control
             }
@@ -62,20 +271,331 @@ where
         }
     }
 
+    /// Build the controls of the view. The control of the focused node takes the focus
+    /// when the focused node or its focus request changed since the last view.
+    ///
+    /// The tab_index attribute is not used, the terminal moves the focus in the order
+    /// of the controls.
+    fn build_root(
+        view: crate::Node<MSG>,
+        last_focus_target: Option<&FocusTarget>,
+        focus_msgs: &FocusMsgs<MSG>,
+    ) -> (Box<dyn titik::Widget<MSG>>, Option<FocusTarget>) {
+        let focus_target = focus::focus_target(&view);
+        THEME.with(|theme| theme.replace(Theme::default()));
+        NODE_PATH.with(|node_path| node_path.replace(vec![0]));
+        let mut root_node = Self::from_node_tree(view, focus_msgs);
+        let focused_control = FOCUSED_CONTROL.with(|focused| focused.take());
+        if focus_target.is_some() && focus_target.as_ref() != last_focus_target {
+            let node_idx = focused_control
+                .map(|address| control_idx(root_node.as_ref(), address, &mut 0))
+                .flatten();
+            if let Some(node_idx) = node_idx {
+                set_focused_node(root_node.as_mut(), node_idx);
+            }
+        }
+        (root_node, focus_target)
+    }
+
+    /// the style inherited from the parent, overridden by the theme
+    /// and then by the style attribute of the widget
+    fn widget_style(widget: &Widget, attrs: &Vec<Attribute<MSG>>) -> WidgetStyle {
+        let inherited = INHERITED_STYLE.with(|inherited| inherited.borrow().clone());
+        let themed = THEME.with(|theme| theme.borrow().style_for(widget));
+        inherited
+            .merge(&themed)
+            .merge(&WidgetStyle::find(attrs).unwrap_or_default())
+    }
+
+    /// titik has no grid layout, so the grid is laid out as rows of horizontal flexbox
+    /// where each child is stretched proportional to the number of columns it spans.
+    /// Empty cells are filled with spacers so the columns of each row line up.
+    ///
+    /// Children spanning multiple rows only occupy the row they start in.
+    fn grid_rows(
+        children: Vec<crate::Node<MSG>>,
+        focus_msgs: &FocusMsgs<MSG>,
+    ) -> Vec<Box<dyn titik::Widget<MSG>>> {
+        fn stretch_over<MSG: 'static>(
+            control: Box<dyn titik::Widget<MSG>>,
+            columns: u32,
+        ) -> Box<dyn titik::Widget<MSG>> {
+            Box::new(Styled::new(control, move |style| {
+                style.flex_grow = columns as f32;
+                style.flex_basis = Dimension::Points(0.0);
+            }))
+        }
+
+        let mut rows: BTreeMap<u32, Vec<(GridCell, crate::Node<MSG>)>> = BTreeMap::new();
+        for child in children {
+            let cell = GridCell::from_attrs(&child.get_attributes());
+            rows.entry(cell.row)
+                .or_insert_with(Vec::new)
+                .push((cell, child));
+        }
+        let total_columns = rows
+            .values()
+            .flatten()
+            .map(|(cell, _)| cell.column + cell.column_span)
+            .max()
+            .unwrap_or(0);
+
+        rows.into_iter()
+            .map(|(_row, mut cells)| {
+                cells.sort_by_key(|(cell, _)| cell.column);
+                let mut hbox = FlexBox::new();
+                hbox.horizontal();
+                let mut next_column = 0;
+                for (cell, child) in cells {
+                    if cell.column > next_column {
+                        hbox.add_child(stretch_over(
+                            Box::new(FlexBox::new()),
+                            cell.column - next_column,
+                        ));
+                    }
+                    hbox.add_child(stretch_over(
+                        Self::from_node_tree(child, focus_msgs),
+                        cell.column_span,
+                    ));
+                    next_column = cell.column + cell.column_span;
+                }
+                if total_columns > next_column {
+                    hbox.add_child(stretch_over(
+                        Box::new(FlexBox::new()),
+                        total_columns - next_column,
+                    ));
+                }
+                Box::new(hbox) as Box<dyn titik::Widget<MSG>>
+            })
+            .collect()
+    }
+
+    /// the header of an expander, clicking it toggles the expanded state
+    fn expander_header(attrs: &Vec<Attribute<MSG>>) -> Box<dyn titik::Widget<MSG>> {
+        let label = find_value(AttribKey::Label, attrs)
+            .map(|v| v.to_string())
+            .unwrap_or(String::new());
+        let expanded = find_value(AttribKey::Expanded, attrs)
+            .map(|v| v.as_bool())
+            .flatten()
+            .unwrap_or(false);
+        let marker = if expanded { "▾" } else { "▸" };
+        let mut btn: Button<MSG> = Button::new(&format!("{} {}", marker, label));
+        if let Some(cb) = find_callback(AttribKey::ToggleEvent, attrs) {
+            let cb = cb.clone();
+            let cb2 = cb.reform(move |_event: crossterm::event::Event| {
+                sauron_vdom::event::Event::InputEvent(sauron_vdom::event::InputEvent::new(
+                    (!expanded).to_string(),
+                ))
+            });
+            btn.on_click = vec![cb2];
+        }
+        Box::new(btn)
+    }
+
+    /// the alignment, padding and margin of a row or a column, in cells
+    fn box_layout(
+        control: Box<dyn titik::Widget<MSG>>,
+        attrs: &Vec<Attribute<MSG>>,
+    ) -> Box<dyn titik::Widget<MSG>> {
+        let justify_content = match Alignment::find(attrs) {
+            Alignment::Start => JustifyContent::FlexStart,
+            Alignment::Center => JustifyContent::Center,
+            Alignment::End => JustifyContent::FlexEnd,
+        };
+        let align_items = match Align::find(AttribKey::CrossAlignment, attrs) {
+            Align::Fill => AlignItems::Stretch,
+            Align::Start => AlignItems::FlexStart,
+            Align::Center => AlignItems::Center,
+            Align::End => AlignItems::FlexEnd,
+        };
+        let padding = cell_insets(Insets::find(AttribKey::Padding, attrs));
+        let margin = cell_insets(Insets::find(AttribKey::Margin, attrs));
+        Box::new(Styled::new(control, move |style| {
+            style.justify_content = justify_content;
+            style.align_items = align_items;
+            style.padding = padding;
+            style.margin = margin;
+        }))
+    }
+
+    /// the grow and shrink factors of a child, a growing child which doesn't fill
+    /// the space it takes is centered in it with auto margins
+    fn flex_item(
+        control: Box<dyn titik::Widget<MSG>>,
+        parent: &Widget,
+        attrs: &Vec<Attribute<MSG>>,
+    ) -> Box<dyn titik::Widget<MSG>> {
+        let flex = Flex::from_attrs(attrs);
+        let is_row = match parent {
+            Widget::Hbox | Widget::Hpane | Widget::Toolbar | Widget::StatusBar => true,
+            _ => false,
+        };
+        Box::new(Styled::new(control, move |style| {
+            style.flex_shrink = flex.shrink as f32;
+            if flex.fill || !flex.expands() {
+                style.flex_grow = flex.grow as f32;
+            } else if is_row {
+                style.margin.start = Dimension::Auto;
+                style.margin.end = Dimension::Auto;
+            } else {
+                style.margin.top = Dimension::Auto;
+                style.margin.bottom = Dimension::Auto;
+            }
+        }))
+    }
+
+    /// the minimum and maximum size in pixels, converted to cells
+    fn constrained(
+        control: Box<dyn titik::Widget<MSG>>,
+        constraints: SizeConstraints,
+    ) -> Box<dyn titik::Widget<MSG>> {
+        let cells = |size: Option<f64>, pixels_per_cell: f32| {
+            size.map(|size| Dimension::Points(size as f32 / pixels_per_cell))
+        };
+        let min_width = cells(constraints.min_width, PIXELS_PER_COLUMN);
+        let min_height = cells(constraints.min_height, PIXELS_PER_ROW);
+        let max_width = cells(constraints.max_width, PIXELS_PER_COLUMN);
+        let max_height = cells(constraints.max_height, PIXELS_PER_ROW);
+        Box::new(Styled::new(control, move |style| {
+            if let Some(min_width) = min_width {
+                style.min_size.width = min_width;
+            }
+            if let Some(min_height) = min_height {
+                style.min_size.height = min_height;
+            }
+            if let Some(max_width) = max_width {
+                style.max_size.width = max_width;
+            }
+            if let Some(max_height) = max_height {
+                style.max_size.height = max_height;
+            }
+        }))
+    }
+
+    /// the space in pixels before a child which is not the first in a row or a column
+    fn spaced(
+        control: Box<dyn titik::Widget<MSG>>,
+        parent: &Widget,
+        spacing: f64,
+    ) -> Box<dyn titik::Widget<MSG>> {
+        let is_row = *parent == Widget::Hbox;
+        // added to the margin the child may have
+        fn add_points(dimension: Dimension, points: f32) -> Dimension {
+            match dimension {
+                Dimension::Points(current) => Dimension::Points(current + points),
+                _ => Dimension::Points(points),
+            }
+        }
+        Box::new(Styled::new(control, move |style| {
+            if is_row {
+                style.margin.start =
+                    add_points(style.margin.start, spacing as f32 / PIXELS_PER_COLUMN);
+            } else {
+                style.margin.top = add_points(style.margin.top, spacing as f32 / PIXELS_PER_ROW);
+            }
+        }))
+    }
+
+    /// Place the control over the first child of its parent stack according to its alignment.
+    /// The insets of an absolutely positioned control can't center it, so the control is put
+    /// in a row which is stretched over the stack and aligns it instead.
+    fn layered(
+        control: Box<dyn titik::Widget<MSG>>,
+        attrs: &Vec<Attribute<MSG>>,
+    ) -> Box<dyn titik::Widget<MSG>> {
+        let horizontal_align = Align::find(AttribKey::HorizontalAlign, attrs);
+        let justify_content = match horizontal_align {
+            Align::Fill | Align::Start => JustifyContent::FlexStart,
+            Align::Center => JustifyContent::Center,
+            Align::End => JustifyContent::FlexEnd,
+        };
+        let align_items = match Align::find(AttribKey::VerticalAlign, attrs) {
+            Align::Fill => AlignItems::Stretch,
+            Align::Start => AlignItems::FlexStart,
+            Align::Center => AlignItems::Center,
+            Align::End => AlignItems::FlexEnd,
+        };
+        let control: Box<dyn titik::Widget<MSG>> = match horizontal_align {
+            Align::Fill => Box::new(Styled::new(control, |style| style.flex_grow = 1.0)),
+            _ => control,
+        };
+        let mut layer = FlexBox::new();
+        layer.horizontal();
+        layer.add_child(control);
+        Box::new(Styled::new(Box::new(layer), move |style| {
+            style.position_type = PositionType::Absolute;
+            style.position = Rect {
+                start: Dimension::Points(0.0),
+                end: Dimension::Points(0.0),
+                top: Dimension::Points(0.0),
+                bottom: Dimension::Points(0.0),
+            };
+            style.justify_content = justify_content;
+            style.align_items = align_items;
+        }))
+    }
+
+    /// emit the toggle listener of a checkbox or a radio with the state it is toggled to
+    fn toggled(
+        control: Box<dyn titik::Widget<MSG>>,
+        toggled_state: bool,
+        attrs: &Vec<Attribute<MSG>>,
+    ) -> Box<dyn titik::Widget<MSG>> {
+        match find_callback(AttribKey::ToggleEvent, attrs) {
+            Some(cb) => {
+                let on_toggle = cb.clone().reform(move |_event: crossterm::event::Event| {
+                    sauron_vdom::event::Event::InputEvent(sauron_vdom::event::InputEvent::new(
+                        toggled_state.to_string(),
+                    ))
+                });
+                Box::new(Toggled::new(control, vec![on_toggle]))
+            }
+            None => control,
+        }
+    }
+
     fn from_node(widget: Widget, attrs: &Vec<Attribute<MSG>>) -> Box<dyn titik::Widget<MSG>>
     where
         MSG: Debug + 'static,
     {
         match widget {
-            Widget::Vbox => {
+            // only one window of an application is shown, see `screen`
+            Widget::Application => {
+                let mut vbox = FlexBox::new();
+                vbox.vertical();
+                Box::new(vbox)
+            }
+            Widget::Window => {
                 let mut vbox = FlexBox::new();
                 vbox.vertical();
                 Box::new(vbox)
             }
+            Widget::Vbox => {
+                let mut vbox = FlexBox::new();
+                vbox.vertical();
+                Self::box_layout(Box::new(vbox), attrs)
+            }
             Widget::Hbox => {
                 let mut hbox = FlexBox::new();
                 hbox.horizontal();
-                Box::new(hbox)
+                Self::box_layout(Box::new(hbox), attrs)
+            }
+            // the rows of the grid are added in from_node_tree
+            Widget::Grid => {
+                let mut vbox = FlexBox::new();
+                vbox.vertical();
+                Box::new(vbox)
+            }
+            Widget::Stack => {
+                let mut vbox = FlexBox::new();
+                vbox.vertical();
+                Box::new(vbox)
+            }
+            Widget::Pages => {
+                let mut vbox = FlexBox::new();
+                vbox.vertical();
+                Box::new(vbox)
             }
             //TOD: make a draggable pane for titik
             Widget::Vpane => {
@@ -94,19 +614,14 @@ where
                     .unwrap_or(String::new());
 
                 let mut btn: Button<MSG> = Button::new(&label);
-                if let Some(cb) = find_callback(AttribKey::ClickEvent, &attrs) {
-                    fn map_event_from_crossterm(
-                        event: crossterm::event::Event,
-                    ) -> sauron_vdom::Event {
-                        sauron_vdom::event::Event::MouseEvent(
-                            sauron_vdom::event::MouseEvent::click(1, 1),
-                        )
+                match find_callback(AttribKey::ClickEvent, &attrs) {
+                    Some(cb) => {
+                        let click_listener = ClickListener::default();
+                        btn.on_click = vec![click_listener.listener(cb)];
+                        Box::new(click_listener.wrap(Box::new(btn)))
                     }
-                    let cb = cb.clone();
-                    let cb2 = cb.reform(map_event_from_crossterm);
-                    btn.on_click = vec![cb2];
+                    None => Box::new(btn),
                 }
-                Box::new(btn)
             }
             Widget::Paragraph => {
                 let value = find_value(AttribKey::Value, &attrs)
@@ -119,59 +634,234 @@ where
                 let value = find_value(AttribKey::Value, &attrs)
                     .map(|v| v.to_string())
                     .unwrap_or(String::new());
-                let input = TextInput::new(value);
-                Box::new(input)
+                let password = find_value(AttribKey::Password, &attrs)
+                    .map(|v| v.as_bool())
+                    .flatten()
+                    .unwrap_or(false);
+                let input: Box<dyn titik::Widget<MSG>> = Box::new(TextInput::new(value.clone()));
+                // the terminal has no clipboard access to guard and no reveal toggle,
+                // only the drawn characters are masked
+                if password {
+                    Box::new(Masked::new(input, &value))
+                } else {
+                    input
+                }
             }
             Widget::Checkbox => {
                 let label = find_value(AttribKey::Label, &attrs)
                     .map(|v| v.to_string())
                     .unwrap_or(String::new());
 
-                let value = find_value(AttribKey::Value, &attrs)
-                    .map(|v| v.as_bool())
-                    .flatten()
-                    .unwrap_or(false);
+                let checked = state::is_checked(&attrs);
 
                 let mut cb = Checkbox::new(&label);
-                cb.set_checked(value);
-                Box::new(cb)
+                cb.set_checked(checked);
+                Self::toggled(Box::new(cb), !checked, &attrs)
             }
-            Widget::Radio => {
+            // keeps its height at the top of the window instead of shrinking with the content
+            Widget::Toolbar => {
+                let mut hbox = FlexBox::new();
+                hbox.horizontal();
+                Box::new(Styled::new(Box::new(hbox), |style| {
+                    style.flex_shrink = 0.0;
+                }))
+            }
+            // a line fixed at the bottom of the parent, the segments are bordered text
+            Widget::StatusBar => {
+                let mut hbox = FlexBox::new();
+                hbox.horizontal();
+                Box::new(Styled::new(Box::new(hbox), |style| {
+                    style.position_type = PositionType::Absolute;
+                    style.position = Rect {
+                        start: Dimension::Points(0.0),
+                        end: Dimension::Points(0.0),
+                        top: Dimension::Undefined,
+                        bottom: Dimension::Points(0.0),
+                    };
+                    style.size.height = Dimension::Points(STATUS_BAR_HEIGHT);
+                }))
+            }
+            Widget::DatePicker => {
+                let mut date_picker: DatePicker<MSG> = DatePicker::new(Date::find(&attrs));
+                if let Some(cb) = find_callback(AttribKey::ChangeEvent, &attrs) {
+                    date_picker.on_change = vec![cb.clone()];
+                }
+                Box::new(date_picker)
+            }
+            Widget::TimePicker => {
+                let time = Time::find(&attrs).unwrap_or_default();
+                let mut time_picker: TimePicker<MSG> = TimePicker::new(time);
+                if let Some(cb) = find_callback(AttribKey::ChangeEvent, &attrs) {
+                    time_picker.on_change = vec![cb.clone()];
+                }
+                Box::new(time_picker)
+            }
+            Widget::Link => {
                 let label = find_value(AttribKey::Label, &attrs)
                     .map(|v| v.to_string())
                     .unwrap_or(String::new());
+                let uri = find_value(AttribKey::Uri, &attrs).map(|v| v.to_string());
 
+                let mut link: Link<MSG> = Link::new(label, uri);
+                match find_callback(AttribKey::ClickEvent, &attrs) {
+                    Some(cb) => {
+                        let click_listener = ClickListener::default();
+                        link.on_click = vec![click_listener.listener(cb)];
+                        Box::new(click_listener.wrap(Box::new(link)))
+                    }
+                    None => Box::new(link),
+                }
+            }
+            Widget::ToolButton => {
+                let mut btn: Button<MSG> = Button::new(&tool_item_label(&attrs));
+                match find_callback(AttribKey::ClickEvent, &attrs) {
+                    Some(cb) => {
+                        let click_listener = ClickListener::default();
+                        btn.on_click = vec![click_listener.listener(cb)];
+                        Box::new(click_listener.wrap(Box::new(btn)))
+                    }
+                    None => Box::new(btn),
+                }
+            }
+            Widget::ToolToggle => {
+                let value = find_value(AttribKey::Value, &attrs)
+                    .map(|v| v.as_bool())
+                    .flatten()
+                    .unwrap_or(false);
+                let marker = if value { "[x]" } else { "[ ]" };
+                let mut btn: Button<MSG> =
+                    Button::new(&format!("{} {}", marker, tool_item_label(&attrs)));
+                if let Some(cb) = find_callback(AttribKey::ToggleEvent, &attrs) {
+                    let cb = cb.clone();
+                    let cb2 = cb.reform(move |_event: crossterm::event::Event| {
+                        sauron_vdom::event::Event::InputEvent(sauron_vdom::event::InputEvent::new(
+                            (!value).to_string(),
+                        ))
+                    });
+                    btn.on_click = vec![cb2];
+                }
+                Box::new(btn)
+            }
+            Widget::Separator => Box::new(Separator::new(layout::Orientation::find(&attrs))),
+            Widget::Space => {
+                let flexible = layout::is_flexible_space(&attrs);
+                let size = |key, pixels_per_cell| {
+                    find_value(key, &attrs)
+                        .map(|v| v.as_f64())
+                        .flatten()
+                        .map(|v| Dimension::Points(v as f32 / pixels_per_cell))
+                        .unwrap_or(Dimension::Auto)
+                };
+                let width = size(AttribKey::Width, PIXELS_PER_COLUMN);
+                let height = size(AttribKey::Height, PIXELS_PER_ROW);
+                Box::new(Styled::new(Box::new(FlexBox::new()), move |style| {
+                    if flexible {
+                        style.flex_grow = 1.0;
+                    } else {
+                        style.size.width = width;
+                        style.size.height = height;
+                        style.flex_shrink = 0.0;
+                    }
+                }))
+            }
+            Widget::ToolSeparator => Box::new(Styled::new(Box::new(FlexBox::new()), |style| {
+                style.size.width = Dimension::Points(1.0);
+            })),
+            Widget::StatusSegment => {
+                let value = find_value(AttribKey::Value, &attrs)
+                    .map(|v| v.to_string())
+                    .unwrap_or(String::new());
+                let textarea = TextArea::new(value);
+                Box::new(Styled::new(Box::new(textarea), |style| {
+                    style.flex_grow = 1.0;
+                }))
+            }
+            // the header and the children are added in from_node_tree
+            Widget::Expander => {
+                let mut vbox = FlexBox::new();
+                vbox.vertical();
+                Box::new(vbox)
+            }
+            Widget::Switch => {
                 let value = find_value(AttribKey::Value, &attrs)
                     .map(|v| v.as_bool())
                     .flatten()
                     .unwrap_or(false);
 
+                let label = if value { "[on ]" } else { "[ off]" };
+                let mut btn: Button<MSG> = Button::new(label);
+                if let Some(cb) = find_callback(AttribKey::ToggleEvent, &attrs) {
+                    let cb = cb.clone();
+                    let cb2 = cb.reform(move |_event: crossterm::event::Event| {
+                        sauron_vdom::event::Event::InputEvent(sauron_vdom::event::InputEvent::new(
+                            (!value).to_string(),
+                        ))
+                    });
+                    btn.on_click = vec![cb2];
+                }
+                Box::new(btn)
+            }
+            Widget::Radio => {
+                let label = find_value(AttribKey::Label, &attrs)
+                    .map(|v| v.to_string())
+                    .unwrap_or(String::new());
+
+                let checked = state::is_checked(&attrs);
+
                 let mut rb = Radio::new(label);
-                rb.set_checked(value);
-                Box::new(rb)
+                rb.set_checked(checked);
+                // a radio is checked when it is selected, it is not unchecked by selecting it again
+                Self::toggled(Box::new(rb), true, &attrs)
             }
             Widget::Image => {
-                let empty = vec![];
-                let bytes = find_value(AttribKey::Data, &attrs)
-                    .map(|v| v.as_bytes())
-                    .flatten()
-                    .unwrap_or(&empty);
-                let image = image::load_from_memory(&bytes).expect("should load");
-                let (width, height) = image.dimensions();
-                let mut img = Image::new(bytes.to_vec());
-                //TODO: get the image size, divide by 10
-                let (width, height) = image.dimensions();
-                img.set_size(Some(width as f32 / 10.0), Some(height as f32 / 10.0 / 2.0));
+                let image = match find_value(AttribKey::Source, &attrs) {
+                    Some(source) => source_image(&source.to_string()),
+                    None => {
+                        let empty = vec![];
+                        let bytes = find_value(AttribKey::Data, &attrs)
+                            .map(|v| v.as_bytes())
+                            .flatten()
+                            .unwrap_or(&empty);
+                        util::decode_image(&bytes).map(Rc::new)
+                    }
+                };
+                let image = match image {
+                    Some(image) => image,
+                    None => return Box::new(FlexBox::new()),
+                };
+                let (width, height) = image_box(&attrs, image.dimensions());
+                let fitted = util::fit_image(&image, Fit::find(&attrs), (width, height));
+                let mut png = vec![];
+                fitted
+                    .write_to(&mut png, ImageOutputFormat::Png)
+                    .expect("should encode");
+                let mut img = Image::new(png);
+                img.set_size(
+                    Some(width as f32 / PIXELS_PER_COLUMN),
+                    Some(height as f32 / PIXELS_PER_ROW),
+                );
                 Box::new(img)
             }
             Widget::Svg => {
-                let empty = vec![];
-                let bytes = find_value(AttribKey::Data, &attrs)
-                    .map(|v| v.as_bytes())
-                    .flatten()
-                    .unwrap_or(&empty);
-                let svg = String::from_utf8(bytes.to_vec()).unwrap_or(String::new());
-                Box::new(SvgImage::new(svg))
+                let bytes = match find_value(AttribKey::Source, &attrs) {
+                    Some(source) => util::source_bytes(&source.to_string())
+                        .map(|bytes| bytes.to_vec())
+                        .unwrap_or_default(),
+                    None => find_value(AttribKey::Data, &attrs)
+                        .map(|v| v.as_bytes().map(|bytes| bytes.to_vec()))
+                        .flatten()
+                        .unwrap_or_default(),
+                };
+                let svg = String::from_utf8(bytes).unwrap_or(String::new());
+                let mut svg_image = SvgImage::new(svg);
+                let width = find_value(AttribKey::Width, &attrs)
+                    .map(|v| v.as_f64().map(|v| v as f32 / PIXELS_PER_COLUMN))
+                    .flatten();
+                let height = find_value(AttribKey::Height, &attrs)
+                    .map(|v| v.as_f64().map(|v| v as f32 / PIXELS_PER_ROW))
+                    .flatten();
+                svg_image.set_size(width, height);
+                Box::new(svg_image)
             }
             Widget::TextArea => {
                 let value = find_value(AttribKey::Value, &attrs)
@@ -191,24 +881,195 @@ where
     }
 }
 
+/// insets in pixels converted to cells
+fn cell_insets(insets: Insets) -> Rect<Dimension> {
+    Rect {
+        start: Dimension::Points(insets.start as f32 / PIXELS_PER_COLUMN),
+        end: Dimension::Points(insets.end as f32 / PIXELS_PER_COLUMN),
+        top: Dimension::Points(insets.top as f32 / PIXELS_PER_ROW),
+        bottom: Dimension::Points(insets.bottom as f32 / PIXELS_PER_ROW),
+    }
+}
+
+/// decode the image file of the source, each file is only decoded once
+/// since the view is rebuilt on every update
+fn source_image(source: &str) -> Option<Rc<DynamicImage>> {
+    let path = util::source_path(source);
+    DECODED_IMAGES.with(|cache| {
+        if let Some(image) = cache.borrow().get(&path) {
+            return Some(Rc::clone(image));
+        }
+        match image::open(&path) {
+            Ok(image) => {
+                let image = Rc::new(image);
+                cache.borrow_mut().insert(path, Rc::clone(&image));
+                Some(image)
+            }
+            Err(e) => {
+                log::error!("unable to load image {}: {}", path.display(), e);
+                None
+            }
+        }
+    })
+}
+
+/// the index of the control in the layout tree, counting the controls in depth first order
+fn control_idx<MSG>(
+    control: &dyn titik::Widget<MSG>,
+    address: *const (),
+    cur_idx: &mut usize,
+) -> Option<usize> {
+    if control.as_any() as *const dyn Any as *const () == address {
+        return Some(*cur_idx);
+    }
+    for child in control.children().unwrap_or(&[]) {
+        *cur_idx += 1;
+        if let Some(node_idx) = control_idx(child.as_ref(), address, cur_idx) {
+            return Some(node_idx);
+        }
+    }
+    None
+}
+
+/// the label of a tool item, the icon name is shown when there is no label
+/// since the terminal can not show icons
+fn tool_item_label<MSG>(attrs: &Vec<Attribute<MSG>>) -> String
+where
+    MSG: 'static,
+{
+    find_value(AttribKey::Label, attrs)
+        .or_else(|| find_value(AttribKey::Icon, attrs))
+        .map(|v| v.to_string())
+        .unwrap_or(String::new())
+}
+
+/// the widgets which take the focus
+fn is_interactive(widget: &Widget) -> bool {
+    match widget {
+        Widget::Button
+        | Widget::TextInput
+        | Widget::TextArea
+        | Widget::Checkbox
+        | Widget::Switch
+        | Widget::Radio
+        | Widget::DatePicker
+        | Widget::TimePicker
+        | Widget::Link
+        | Widget::ToolButton
+        | Widget::ToolToggle => true,
+        _ => false,
+    }
+}
+
+/// the value given to the focus and blur listeners, taken from the attributes
+/// since the controls are rebuilt from the view after every input
+fn focus_value<MSG>(widget: &Widget, attrs: &Vec<Attribute<MSG>>) -> String
+where
+    MSG: 'static,
+{
+    match widget {
+        Widget::TextInput | Widget::TextArea => find_value(AttribKey::Value, attrs)
+            .map(|v| v.to_string())
+            .unwrap_or(String::new()),
+        Widget::Checkbox | Widget::Radio => state::is_checked(attrs).to_string(),
+        _ => String::new(),
+    }
+}
+
+/// the image box in pixels, from the width and height attributes or else the image size
+fn image_box<MSG>(attrs: &Vec<Attribute<MSG>>, image_size: (u32, u32)) -> (u32, u32)
+where
+    MSG: 'static,
+{
+    let width = find_value(AttribKey::Width, attrs)
+        .map(|v| v.as_f64())
+        .flatten();
+    let height = find_value(AttribKey::Height, attrs)
+        .map(|v| v.as_f64())
+        .flatten();
+    util::box_size(width, height, image_size)
+}
+
+/// The terminal has no way to ask for its colors, most terminals tell the palette
+/// they were configured with in `COLORFGBG` as `foreground;background` color indexes.
+/// Backgrounds with a color index other than white (7) or the bright colors (9 to 15) are dark.
+fn terminal_color_scheme() -> ColorScheme {
+    let background = std::env::var("COLORFGBG")
+        .ok()
+        .map(|colors| {
+            colors
+                .rsplit(';')
+                .next()
+                .map(|background| background.parse::<u8>().ok())
+                .flatten()
+        })
+        .flatten();
+    match background {
+        Some(7) | Some(9..=15) => ColorScheme::Light,
+        Some(_) => ColorScheme::Dark,
+        None => ColorScheme::Light,
+    }
+}
+
+/// the message of the window listening to the color scheme, the terminal doesn't
+/// tell when its palette changes so it is only emitted once
+fn color_scheme_msg<MSG>(vdom: &Node<MSG>) -> Option<MSG>
+where
+    MSG: 'static,
+{
+    let attrs = vdom.get_attributes();
+    find_callback(AttribKey::ColorSchemeEvent, &attrs).map(|cb| {
+        cb.emit(sauron_vdom::event::Event::InputEvent(
+            sauron_vdom::event::InputEvent::new(terminal_color_scheme().as_str().to_string()),
+        ))
+    })
+}
+
+/// set the terminal title from the title of the window, the other window attributes
+/// don't apply to a terminal
+fn set_terminal_title<MSG>(vdom: &Node<MSG>)
+where
+    MSG: 'static,
+{
+    if let Some(title) = WindowConfig::from_root(vdom)
+        .map(|config| config.title)
+        .flatten()
+    {
+        let mut stdout = io::stdout();
+        if let Err(e) = crossterm::execute!(stdout, terminal::SetTitle(&*title)) {
+            log::error!("unable to set the terminal title: {}", e);
+        }
+    }
+}
+
 impl<APP, MSG> Backend<APP, MSG> for TitikBackend<APP, MSG>
 where
     APP: Component<MSG> + 'static,
     MSG: Debug + 'static,
 {
-    fn init(app: APP) -> Self {
-
+    fn init(mut app: APP) -> Self {
         let mut stdout = io::stdout();
-        let vdom = app.view();
-        let root_node = Self::from_node_tree(vdom);
+        let mut vdom = Self::screen(app.view());
+        if let Some(msg) = color_scheme_msg(&vdom) {
+            app.update(msg);
+            vdom = Self::screen(app.view());
+        }
+        set_terminal_title(&vdom);
+        let focus_msgs = FocusMsgs::default();
+        let (root_node, focus_target) = Self::build_root(vdom, None, &focus_msgs);
         let renderer = Renderer::new(root_node);
 
         let mut backend = TitikBackend {
             app: Rc::new(RefCell::new(app)),
-			renderer: Rc::new(RefCell::new(renderer)),
+            renderer: Rc::new(RefCell::new(renderer)),
+            focus_target: RefCell::new(focus_target),
+            focus_msgs,
             _phantom_msg: PhantomData,
         };
-        backend.renderer.borrow_mut().run(&mut stdout, Some(&backend));
+        backend
+            .renderer
+            .borrow_mut()
+            .run(&mut stdout, Some(&backend));
         backend
     }
 }
@@ -219,9 +1080,18 @@ where
     APP: Component<MSG> + 'static,
 {
     fn dispatch(&self, msg: MSG) {
-        println!("dispatching..");
-		let new_view = self.app.borrow().view();
-		let root_node = Self::from_node_tree(new_view);
-		self.renderer.try_borrow_mut().expect("borrow here..").set_root_node(root_node);
+        self.app.borrow_mut().update(msg);
+        let new_view = Self::screen(self.app.borrow().view());
+        set_terminal_title(&new_view);
+        let (root_node, focus_target) = Self::build_root(
+            new_view,
+            self.focus_target.borrow().as_ref(),
+            &self.focus_msgs,
+        );
+        *self.focus_target.borrow_mut() = focus_target;
+        self.renderer
+            .try_borrow_mut()
+            .expect("borrow here..")
+            .set_root_node(root_node);
     }
 }
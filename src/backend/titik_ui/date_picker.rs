@@ -0,0 +1,290 @@
+use crate::widget::date::{self, Date, Time};
+use sauron_vdom::{event::InputEvent, Callback};
+use std::{any::Any, fmt};
+use titik::{
+    crossterm::{
+        event::{Event, KeyCode, KeyEvent, MouseEvent},
+        style::Attribute,
+    },
+    stretch::{
+        geometry::Size,
+        result::Layout,
+        style::{Dimension, Style},
+    },
+    Buffer, Cell, Cmd, LayoutTree, Widget as Control,
+};
+
+/// 7 days of 2 digits separated by a space
+const MONTH_GRID_WIDTH: f32 = 20.0;
+/// the month name, the weekday names and up to 6 weeks
+const MONTH_GRID_HEIGHT: f32 = 8.0;
+const WEEKDAYS: &str = "Mo Tu We Th Fr Sa Su";
+
+/// A month grid, the arrow keys move the selected day by a day or a week
+/// and page up and page down move it by a month
+pub(crate) struct DatePicker<MSG> {
+    selected: Option<Date>,
+    /// the month shown, from the selected date or else the current date
+    shown: Date,
+    is_focused: bool,
+    pub(crate) on_change: Vec<Callback<sauron_vdom::Event, MSG>>,
+}
+
+impl<MSG> DatePicker<MSG> {
+    pub(crate) fn new(selected: Option<Date>) -> Self {
+        DatePicker {
+            selected,
+            shown: selected.unwrap_or_else(Date::today),
+            is_focused: false,
+            on_change: vec![],
+        }
+    }
+
+    /// the picked date is emitted, the grid only moves when the view changes the value
+    fn emit_change(&self, date: Date) -> Vec<MSG> {
+        self.on_change
+            .iter()
+            .map(|cb| cb.emit(InputEvent::new(date.to_string())))
+            .collect()
+    }
+
+    /// the day drawn at the column and row relative to the grid
+    fn day_at(&self, column: usize, row: usize) -> Option<Date> {
+        if row < 2 {
+            return None;
+        }
+        let first = self.shown.first_of_month();
+        let cell = (row - 2) * 7 + column / 3;
+        let day = cell as i64 - first.weekday() as i64 + 1;
+        if day >= 1 && day <= date::days_in_month(first.year, first.month) as i64 {
+            Date::new(first.year, first.month, day as u32)
+        } else {
+            None
+        }
+    }
+}
+
+impl<MSG> fmt::Debug for DatePicker<MSG> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DatePicker({:?})", self.selected)
+    }
+}
+
+impl<MSG> Control<MSG> for DatePicker<MSG>
+where
+    MSG: 'static,
+{
+    fn style(&self) -> Style {
+        Style {
+            size: Size {
+                width: Dimension::Points(MONTH_GRID_WIDTH),
+                height: Dimension::Points(MONTH_GRID_HEIGHT),
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn add_child(&mut self, _child: Box<dyn Control<MSG>>) -> bool {
+        false
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        None
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        None
+    }
+
+    fn draw(&self, buf: &mut Buffer, layout_tree: &LayoutTree) -> Vec<Cmd> {
+        let layout = layout_tree.layout;
+        let loc_x = layout.location.x.round() as usize;
+        let loc_y = layout.location.y.round() as usize;
+
+        let title = format!("{} {}", date::month_name(self.shown.month), self.shown.year);
+        let title = format!("{:^width$}", title, width = MONTH_GRID_WIDTH as usize);
+        let title_attributes: &[Attribute] = if self.is_focused {
+            &[Attribute::Bold]
+        } else {
+            &[]
+        };
+        draw_text(buf, loc_x, loc_y, &title, title_attributes);
+        draw_text(buf, loc_x, loc_y + 1, WEEKDAYS, &[Attribute::Underlined]);
+
+        let first = self.shown.first_of_month();
+        for day in 1..=date::days_in_month(first.year, first.month) {
+            let cell = (first.weekday() + day - 1) as usize;
+            let x = loc_x + (cell % 7) * 3;
+            let y = loc_y + 2 + cell / 7;
+            let is_selected = self.selected == Date::new(first.year, first.month, day);
+            let attributes: &[Attribute] = if is_selected {
+                &[Attribute::Reverse]
+            } else {
+                &[]
+            };
+            draw_text(buf, x, y, &format!("{:>2}", day), attributes);
+        }
+        vec![]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, _width: Option<f32>, _height: Option<f32>) {}
+
+    fn process_event(&mut self, event: Event, layout: &Layout) -> Vec<MSG> {
+        let current = self.selected.unwrap_or(self.shown);
+        match event {
+            Event::Key(KeyEvent { code, .. }) => match code {
+                KeyCode::Left => self.emit_change(current.add_days(-1)),
+                KeyCode::Right => self.emit_change(current.add_days(1)),
+                KeyCode::Up => self.emit_change(current.add_days(-7)),
+                KeyCode::Down => self.emit_change(current.add_days(7)),
+                KeyCode::PageUp => self.emit_change(current.add_months(-1)),
+                KeyCode::PageDown => self.emit_change(current.add_months(1)),
+                KeyCode::Enter if self.selected.is_none() => self.emit_change(current),
+                _ => vec![],
+            },
+            Event::Mouse(MouseEvent::Down(_, x, y, _)) => {
+                let column = (x as f32 - layout.location.x.round()).max(0.0) as usize;
+                let row = (y as f32 - layout.location.y.round()).max(0.0) as usize;
+                match self.day_at(column, row) {
+                    Some(date) => self.emit_change(date),
+                    None => vec![],
+                }
+            }
+            _ => vec![],
+        }
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+    }
+}
+
+/// The hour and the minute, the up and down keys move the time by a minute
+/// and page up and page down move it by an hour
+pub(crate) struct TimePicker<MSG> {
+    time: Time,
+    is_focused: bool,
+    pub(crate) on_change: Vec<Callback<sauron_vdom::Event, MSG>>,
+}
+
+impl<MSG> TimePicker<MSG> {
+    pub(crate) fn new(time: Time) -> Self {
+        TimePicker {
+            time,
+            is_focused: false,
+            on_change: vec![],
+        }
+    }
+
+    fn emit_change(&self, time: Time) -> Vec<MSG> {
+        self.on_change
+            .iter()
+            .map(|cb| cb.emit(InputEvent::new(time.to_string())))
+            .collect()
+    }
+}
+
+impl<MSG> fmt::Debug for TimePicker<MSG> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TimePicker({})", self.time)
+    }
+}
+
+impl<MSG> Control<MSG> for TimePicker<MSG>
+where
+    MSG: 'static,
+{
+    /// the time is shown as `HH:MM` between arrows hinting at the keys
+    fn style(&self) -> Style {
+        Style {
+            size: Size {
+                width: Dimension::Points(9.0),
+                height: Dimension::Points(1.0),
+            },
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn add_child(&mut self, _child: Box<dyn Control<MSG>>) -> bool {
+        false
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        None
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        None
+    }
+
+    fn draw(&self, buf: &mut Buffer, layout_tree: &LayoutTree) -> Vec<Cmd> {
+        let layout = layout_tree.layout;
+        let loc_x = layout.location.x.round() as usize;
+        let loc_y = layout.location.y.round() as usize;
+        let attributes: &[Attribute] = if self.is_focused {
+            &[Attribute::Reverse]
+        } else {
+            &[Attribute::Underlined]
+        };
+        draw_text(buf, loc_x, loc_y, "▴", &[]);
+        draw_text(buf, loc_x + 2, loc_y, &self.time.to_string(), attributes);
+        draw_text(buf, loc_x + 8, loc_y, "▾", &[]);
+        vec![]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, _width: Option<f32>, _height: Option<f32>) {}
+
+    fn process_event(&mut self, event: Event, layout: &Layout) -> Vec<MSG> {
+        match event {
+            Event::Key(KeyEvent { code, .. }) => match code {
+                KeyCode::Up => self.emit_change(self.time.add_minutes(1)),
+                KeyCode::Down => self.emit_change(self.time.add_minutes(-1)),
+                KeyCode::PageUp => self.emit_change(self.time.add_minutes(60)),
+                KeyCode::PageDown => self.emit_change(self.time.add_minutes(-60)),
+                _ => vec![],
+            },
+            // the arrows at both ends are clickable
+            Event::Mouse(MouseEvent::Down(_, x, _, _)) => {
+                match (x as f32 - layout.location.x.round()) as i32 {
+                    0 => self.emit_change(self.time.add_minutes(1)),
+                    8 => self.emit_change(self.time.add_minutes(-1)),
+                    _ => vec![],
+                }
+            }
+            _ => vec![],
+        }
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+    }
+}
+
+/// one character per cell, with the same attributes
+fn draw_text(buf: &mut Buffer, x: usize, y: usize, text: &str, attributes: &[Attribute]) {
+    for (i, ch) in text.chars().enumerate() {
+        let mut cell = Cell::new(ch.to_string());
+        for attribute in attributes {
+            cell.attribute(*attribute);
+        }
+        buf.set_cell(x + i, y, cell);
+    }
+}
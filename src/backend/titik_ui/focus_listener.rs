@@ -0,0 +1,99 @@
+use super::wrapper::{Wrap, Wrapped};
+use sauron_vdom::{event::InputEvent, Callback};
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+use titik::{crossterm::event::Event, stretch::result::Layout, Widget as Control};
+
+/// The focus and blur messages of the controls of a view, waiting to be returned
+/// with the event which moved the focus.
+pub(crate) type FocusMsgs<MSG> = Rc<RefCell<Vec<MSG>>>;
+
+thread_local! {
+    /// The paths of the nodes whose control titik last told it has the focus. The controls
+    /// are rebuilt on every view, a rebuilt control which titik gives the focus back to
+    /// finds its node here and doesn't emit its focus listener again.
+    static FOCUSED_PATHS: RefCell<HashSet<Vec<usize>>> = RefCell::new(HashSet::new());
+}
+
+/// Wraps a titik control and emits its focus and blur listeners when the focus
+/// moves to it or away from it.
+///
+/// Titik only tells a control that its focus changed, and the control has no way to give
+/// back a message then, so the messages are kept with the ones of the other controls of the
+/// view and returned by the next event given to a control wrapped in a FocusListener.
+/// All the interactive controls are wrapped, so the blur of a control and the focus of
+/// another one are returned with the click or the key which moved the focus.
+pub(crate) struct FocusListener<MSG> {
+    /// the path of the node the control is built for, see FOCUSED_PATHS
+    path: Vec<usize>,
+    /// the value given to the listeners, it is the value of the view the control is built for
+    value: String,
+    on_focus: Option<Callback<sauron_vdom::Event, MSG>>,
+    on_blur: Option<Callback<sauron_vdom::Event, MSG>>,
+    focus_msgs: FocusMsgs<MSG>,
+}
+
+impl<MSG> FocusListener<MSG>
+where
+    MSG: 'static,
+{
+    pub(crate) fn new(
+        inner: Box<dyn Control<MSG>>,
+        path: Vec<usize>,
+        value: String,
+        on_focus: Option<Callback<sauron_vdom::Event, MSG>>,
+        on_blur: Option<Callback<sauron_vdom::Event, MSG>>,
+        focus_msgs: &FocusMsgs<MSG>,
+    ) -> Wrapped<MSG, Self> {
+        Wrapped::new(
+            inner,
+            FocusListener {
+                path,
+                value,
+                on_focus,
+                on_blur,
+                focus_msgs: focus_msgs.clone(),
+            },
+        )
+    }
+}
+
+impl<MSG> Wrap<MSG> for FocusListener<MSG>
+where
+    MSG: 'static,
+{
+    const NAME: &'static str = "FocusListener";
+
+    fn process_event(
+        &mut self,
+        inner: &mut dyn Control<MSG>,
+        event: Event,
+        layout: &Layout,
+    ) -> Vec<MSG> {
+        let mut msgs = self.focus_msgs.replace(vec![]);
+        msgs.extend(inner.process_event(event, layout));
+        msgs
+    }
+
+    fn set_focused(&mut self, inner: &mut dyn Control<MSG>, focused: bool) {
+        inner.set_focused(focused);
+        let changed = FOCUSED_PATHS.with(|focused_paths| {
+            let mut focused_paths = focused_paths.borrow_mut();
+            if focused {
+                focused_paths.insert(self.path.clone())
+            } else {
+                focused_paths.remove(&self.path)
+            }
+        });
+        if changed {
+            let cb = if focused {
+                &self.on_focus
+            } else {
+                &self.on_blur
+            };
+            if let Some(cb) = cb {
+                let msg = cb.emit(InputEvent::new(self.value.clone()));
+                self.focus_msgs.borrow_mut().push(msg);
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use super::wrapper::{Wrap, Wrapped};
+use crate::widget::key::{self, Modifiers};
+use sauron_vdom::Callback;
+use titik::{
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+    stretch::result::Layout,
+    Widget as Control,
+};
+
+/// Wraps a titik control and emits its key down listeners for the keys it receives,
+/// the keys are given to the wrapped control too.
+///
+/// The terminal only tells when a key is pressed, there are no key up events
+/// and a key held down is not told apart from a key pressed again.
+pub(crate) struct KeyListener<MSG> {
+    on_key_down: Vec<Callback<sauron_vdom::Event, MSG>>,
+}
+
+impl<MSG> KeyListener<MSG> {
+    pub(crate) fn new(
+        inner: Box<dyn Control<MSG>>,
+        on_key_down: Vec<Callback<sauron_vdom::Event, MSG>>,
+    ) -> Wrapped<MSG, Self> {
+        Wrapped::new(inner, KeyListener { on_key_down })
+    }
+}
+
+impl<MSG> Wrap<MSG> for KeyListener<MSG> {
+    const NAME: &'static str = "KeyListener";
+
+    fn process_event(
+        &mut self,
+        inner: &mut dyn Control<MSG>,
+        event: Event,
+        layout: &Layout,
+    ) -> Vec<MSG> {
+        let mut msgs = vec![];
+        if let Event::Key(key_event) = event {
+            let key_event = crossterm_key_event(key_event);
+            msgs.extend(self.on_key_down.iter().map(|cb| cb.emit(key_event.clone())));
+        }
+        msgs.extend(inner.process_event(event, layout));
+        msgs
+    }
+}
+
+/// The crossterm key named the way the web names it. The terminal only tells the character
+/// typed, its physical key is the one typing it on a US keyboard.
+fn crossterm_key_event(key_event: KeyEvent) -> sauron_vdom::Event {
+    let mut modifiers = Modifiers {
+        ctrl: key_event.modifiers.contains(KeyModifiers::CONTROL),
+        shift: key_event.modifiers.contains(KeyModifiers::SHIFT),
+        alt: key_event.modifiers.contains(KeyModifiers::ALT),
+        meta: false,
+    };
+    let key = match key_event.code {
+        KeyCode::Char(ch) => ch.to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Esc => "Escape".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        // shift tab is reported as a key of its own
+        KeyCode::BackTab => {
+            modifiers.shift = true;
+            "Tab".to_string()
+        }
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Delete => "Delete".to_string(),
+        KeyCode::Insert => "Insert".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PageUp".to_string(),
+        KeyCode::PageDown => "PageDown".to_string(),
+        KeyCode::Up => "ArrowUp".to_string(),
+        KeyCode::Down => "ArrowDown".to_string(),
+        KeyCode::Left => "ArrowLeft".to_string(),
+        KeyCode::Right => "ArrowRight".to_string(),
+        KeyCode::F(number) => format!("F{}", number),
+        KeyCode::Null => "Unidentified".to_string(),
+    };
+    let code = match key_event.code {
+        KeyCode::Char(ch) => key::us_layout_code(ch).unwrap_or_else(|| "Unidentified".to_string()),
+        // the keys which don't type a character are named after their physical key
+        _ => key.clone(),
+    };
+    key::key_event(key, code, modifiers, false)
+}
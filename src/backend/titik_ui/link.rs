@@ -0,0 +1,140 @@
+use sauron_vdom::Callback;
+use std::{any::Any, fmt, process::Command};
+use titik::{
+    crossterm::{
+        event::{Event, KeyCode, KeyEvent, MouseEvent},
+        style::Attribute,
+    },
+    stretch::{
+        geometry::Size,
+        result::Layout,
+        style::{Dimension, Style},
+    },
+    Buffer, Cell, Cmd, LayoutTree, Widget as Control,
+};
+
+/// Underlined text which emits its click listeners when clicked or when enter is pressed,
+/// a link without click listeners opens its uri with the opener of the platform instead.
+///
+/// The terminal can't open the uri itself: the mouse is captured, and the escape sequence
+/// of a terminal hyperlink would be counted as text in the cells it is written in.
+pub(crate) struct Link<MSG> {
+    label: String,
+    uri: Option<String>,
+    is_focused: bool,
+    pub(crate) on_click: Vec<Callback<Event, MSG>>,
+}
+
+impl<MSG> Link<MSG> {
+    pub(crate) fn new(label: String, uri: Option<String>) -> Self {
+        Link {
+            label,
+            uri,
+            is_focused: false,
+            on_click: vec![],
+        }
+    }
+
+    fn activate(&self, event: Event) -> Vec<MSG> {
+        if self.on_click.is_empty() {
+            if let Some(uri) = &self.uri {
+                open_uri(uri);
+            }
+        }
+        self.on_click
+            .iter()
+            .map(|cb| cb.emit(event.clone()))
+            .collect()
+    }
+}
+
+impl<MSG> fmt::Debug for Link<MSG> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Link({:?})", self.label)
+    }
+}
+
+impl<MSG> Control<MSG> for Link<MSG>
+where
+    MSG: 'static,
+{
+    fn style(&self) -> Style {
+        Style {
+            size: Size {
+                width: Dimension::Points(self.label.chars().count() as f32),
+                height: Dimension::Points(1.0),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn add_child(&mut self, _child: Box<dyn Control<MSG>>) -> bool {
+        false
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        None
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        None
+    }
+
+    fn draw(&self, buf: &mut Buffer, layout_tree: &LayoutTree) -> Vec<Cmd> {
+        let layout = layout_tree.layout;
+        let loc_x = layout.location.x.round() as usize;
+        let loc_y = layout.location.y.round() as usize;
+        let width = layout.size.width.round() as usize;
+        for (i, ch) in self.label.chars().take(width).enumerate() {
+            let mut cell = Cell::new(ch.to_string());
+            cell.attribute(Attribute::Underlined);
+            if self.is_focused {
+                cell.attribute(Attribute::Bold);
+            }
+            buf.set_cell(loc_x + i, loc_y, cell);
+        }
+        vec![]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, _width: Option<f32>, _height: Option<f32>) {}
+
+    fn process_event(&mut self, event: Event, _layout: &Layout) -> Vec<MSG> {
+        match event {
+            Event::Mouse(MouseEvent::Down(..)) => self.activate(event),
+            Event::Key(KeyEvent {
+                code: KeyCode::Enter,
+                ..
+            }) => self.activate(event),
+            _ => vec![],
+        }
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+    }
+}
+
+/// open the uri with the program the platform opens uris with, without waiting for it
+fn open_uri(uri: &str) {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        // the empty argument is the title of the window start would open
+        command.args(&["/C", "start", ""]);
+        command
+    } else if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else {
+        Command::new("xdg-open")
+    };
+    if let Err(e) = command.arg(uri).spawn() {
+        log::error!("unable to open {}: {}", uri, e);
+    }
+}
@@ -0,0 +1,57 @@
+use super::wrapper::{Wrap, Wrapped};
+use titik::{Buffer, Cmd, LayoutTree, Widget as Control};
+
+/// Wraps a password text input and draws `*` over the characters it shows.
+/// The text input keeps the real value, so editing it and the input listeners
+/// work on the password and not on the mask.
+pub(crate) struct Masked {
+    /// the number of characters of the value of the view, the spaces in it are masked too
+    length: usize,
+}
+
+impl Masked {
+    pub(crate) fn new<MSG>(inner: Box<dyn Control<MSG>>, value: &str) -> Wrapped<MSG, Self> {
+        Wrapped::new(
+            inner,
+            Masked {
+                length: value.chars().count(),
+            },
+        )
+    }
+}
+
+impl<MSG> Wrap<MSG> for Masked {
+    const NAME: &'static str = "Masked";
+
+    /// The text is drawn on the row inside the border of the text input. The cells up to
+    /// the length of the value are masked whatever they show, and so are the characters
+    /// typed after them which are not in the view yet.
+    fn draw(
+        &self,
+        inner: &dyn Control<MSG>,
+        buf: &mut Buffer,
+        layout_tree: &LayoutTree,
+    ) -> Vec<Cmd> {
+        let cmds = inner.draw(buf, layout_tree);
+        let layout = layout_tree.layout;
+        let loc_x = layout.location.x.round() as usize;
+        let text_y = layout.location.y.round() as usize + 1;
+        let width = layout.size.width.round() as usize;
+        let columns = 1..width.saturating_sub(1);
+        let typed = columns
+            .clone()
+            .filter(|i| {
+                buf.get_cell_mut(loc_x + i, text_y)
+                    .map_or(false, |cell| cell.symbol != " ")
+            })
+            .last()
+            .unwrap_or(0);
+        let masked = self.length.max(typed);
+        for i in columns.take_while(|i| *i <= masked) {
+            if let Some(cell) = buf.get_cell_mut(loc_x + i, text_y) {
+                cell.symbol = "*".to_string();
+            }
+        }
+        cmds
+    }
+}
@@ -0,0 +1,269 @@
+use super::wrapper::{Wrap, Wrapped};
+use crate::widget::{key::Modifiers, mouse};
+use sauron_vdom::{event::MouseButton, Callback};
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+use titik::{
+    crossterm::event::{self, Event, KeyModifiers, MouseEvent},
+    stretch::result::Layout,
+    Widget as Control,
+};
+
+/// two presses of the same cell within this time are a double click
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(500);
+
+/// Counts the presses of the same cell in a row,
+/// the terminal doesn't tell double and triple clicks apart.
+#[derive(Default)]
+struct Presses {
+    /// the time and the cell of the last press
+    last_press: Option<(Instant, u16, u16)>,
+    /// the number of presses in a row up to the last press
+    count: i32,
+}
+
+impl Presses {
+    /// count a press of the cell, the number of presses in a row is returned
+    fn press(&mut self, column: u16, row: u16) -> i32 {
+        let now = Instant::now();
+        let in_a_row = self.last_press.map_or(false, |(time, x, y)| {
+            now.duration_since(time) < DOUBLE_CLICK_TIME && (x, y) == (column, row)
+        });
+        self.count = if in_a_row { self.count + 1 } else { 1 };
+        self.last_press = Some((now, column, row));
+        self.count
+    }
+}
+
+/// the cell of the top left corner of a control in the window
+fn origin(layout: &Layout) -> (i32, i32) {
+    (
+        layout.location.x.round() as i32,
+        layout.location.y.round() as i32,
+    )
+}
+
+/// the listeners of a MouseListener
+pub(crate) struct MouseListeners<MSG> {
+    pub(crate) on_mouse_down: Option<Callback<sauron_vdom::Event, MSG>>,
+    pub(crate) on_mouse_up: Option<Callback<sauron_vdom::Event, MSG>>,
+    pub(crate) on_mouse_move: Option<Callback<sauron_vdom::Event, MSG>>,
+    pub(crate) on_double_click: Option<Callback<sauron_vdom::Event, MSG>>,
+}
+
+impl<MSG> MouseListeners<MSG> {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.on_mouse_down.is_none()
+            && self.on_mouse_up.is_none()
+            && self.on_mouse_move.is_none()
+            && self.on_double_click.is_none()
+    }
+}
+
+/// Wraps a titik control and emits its mouse listeners for the mouse events it receives,
+/// the events are given to the wrapped control too.
+///
+/// The terminal only tells the pointer moved while a button is held down,
+/// and it doesn't tell double clicks apart so they are detected from the presses.
+pub(crate) struct MouseListener<MSG> {
+    listeners: MouseListeners<MSG>,
+    presses: Presses,
+}
+
+impl<MSG> MouseListener<MSG> {
+    pub(crate) fn new(
+        inner: Box<dyn Control<MSG>>,
+        listeners: MouseListeners<MSG>,
+    ) -> Wrapped<MSG, Self> {
+        Wrapped::new(
+            inner,
+            MouseListener {
+                listeners,
+                presses: Presses::default(),
+            },
+        )
+    }
+
+    fn emit(
+        cb: &Option<Callback<sauron_vdom::Event, MSG>>,
+        r#type: &'static str,
+        mouse_event: MouseEvent,
+        layout: &Layout,
+        click_count: i32,
+    ) -> Option<MSG> {
+        cb.as_ref().map(|cb| {
+            cb.emit(crossterm_mouse_event(
+                r#type,
+                mouse_event,
+                origin(layout),
+                click_count,
+            ))
+        })
+    }
+}
+
+impl<MSG> Wrap<MSG> for MouseListener<MSG> {
+    const NAME: &'static str = "MouseListener";
+
+    fn process_event(
+        &mut self,
+        inner: &mut dyn Control<MSG>,
+        event: Event,
+        layout: &Layout,
+    ) -> Vec<MSG> {
+        let mut msgs = vec![];
+        if let Event::Mouse(mouse_event) = event {
+            match mouse_event {
+                MouseEvent::Down(_, column, row, _) => {
+                    let click_count = self.presses.press(column, row);
+                    msgs.extend(Self::emit(
+                        &self.listeners.on_mouse_down,
+                        "mousedown",
+                        mouse_event,
+                        layout,
+                        click_count,
+                    ));
+                    if click_count == 2 {
+                        msgs.extend(Self::emit(
+                            &self.listeners.on_double_click,
+                            "dblclick",
+                            mouse_event,
+                            layout,
+                            click_count,
+                        ));
+                    }
+                }
+                MouseEvent::Up(..) => msgs.extend(Self::emit(
+                    &self.listeners.on_mouse_up,
+                    "mouseup",
+                    mouse_event,
+                    layout,
+                    self.presses.count,
+                )),
+                MouseEvent::Drag(..) => msgs.extend(Self::emit(
+                    &self.listeners.on_mouse_move,
+                    "mousemove",
+                    mouse_event,
+                    layout,
+                    0,
+                )),
+                _ => (),
+            }
+        }
+        msgs.extend(inner.process_event(event, layout));
+        msgs
+    }
+}
+
+/// The mouse event of a crossterm event, the position is in cells.
+/// The origin is the cell of the top left corner of the widget in the window.
+pub(crate) fn crossterm_mouse_event(
+    r#type: &'static str,
+    mouse_event: MouseEvent,
+    origin: (i32, i32),
+    click_count: i32,
+) -> sauron_vdom::Event {
+    let (button, column, row, modifiers) = match mouse_event {
+        MouseEvent::Down(button, column, row, modifiers)
+        | MouseEvent::Up(button, column, row, modifiers)
+        | MouseEvent::Drag(button, column, row, modifiers) => {
+            let button = match button {
+                event::MouseButton::Left => MouseButton::Left,
+                event::MouseButton::Right => MouseButton::Right,
+                event::MouseButton::Middle => MouseButton::Middle,
+            };
+            (button, column, row, modifiers)
+        }
+        MouseEvent::ScrollDown(column, row, modifiers) => {
+            (MouseButton::WheelDown, column, row, modifiers)
+        }
+        MouseEvent::ScrollUp(column, row, modifiers) => {
+            (MouseButton::WheelUp, column, row, modifiers)
+        }
+    };
+    let window_position = (column as i32, row as i32);
+    let widget_position = (window_position.0 - origin.0, window_position.1 - origin.1);
+    let modifiers = Modifiers {
+        ctrl: modifiers.contains(KeyModifiers::CONTROL),
+        shift: modifiers.contains(KeyModifiers::SHIFT),
+        alt: modifiers.contains(KeyModifiers::ALT),
+        meta: false,
+    };
+    mouse::mouse_event(
+        r#type,
+        widget_position,
+        window_position,
+        button,
+        modifiers,
+        click_count,
+    )
+}
+
+/// The last click of a titik control: its origin and its click count
+#[derive(Clone, Copy, Default)]
+struct Click {
+    origin: (i32, i32),
+    count: i32,
+}
+
+/// Wraps a titik control which has click listeners and records where the control is
+/// and how many times it was pressed in a row when it is given a mouse event,
+/// since titik gives its click listeners neither the layout nor the click count.
+#[derive(Default)]
+pub(crate) struct ClickListener {
+    last_click: Rc<Cell<Click>>,
+    presses: Presses,
+}
+
+impl ClickListener {
+    /// The click listener to give to the control, the position of the mouse event is
+    /// in the control. A control activated with the keyboard gets an event
+    /// without a position.
+    pub(crate) fn listener<MSG>(
+        &self,
+        cb: &Callback<sauron_vdom::Event, MSG>,
+    ) -> Callback<Event, MSG>
+    where
+        MSG: 'static,
+    {
+        let last_click = self.last_click.clone();
+        cb.clone().reform(move |event| match event {
+            Event::Mouse(mouse_event) => {
+                let click = last_click.get();
+                crossterm_mouse_event("click", mouse_event, click.origin, click.count)
+            }
+            _ => sauron_vdom::Event::MouseEvent(sauron_vdom::event::MouseEvent::default()),
+        })
+    }
+
+    /// wrap the control the listener is given to
+    pub(crate) fn wrap<MSG>(self, inner: Box<dyn Control<MSG>>) -> Wrapped<MSG, Self> {
+        Wrapped::new(inner, self)
+    }
+}
+
+impl<MSG> Wrap<MSG> for ClickListener {
+    const NAME: &'static str = "ClickListener";
+
+    fn process_event(
+        &mut self,
+        inner: &mut dyn Control<MSG>,
+        event: Event,
+        layout: &Layout,
+    ) -> Vec<MSG> {
+        if let Event::Mouse(mouse_event) = event {
+            let count = match mouse_event {
+                MouseEvent::Down(_, column, row, _) => self.presses.press(column, row),
+                _ => self.presses.count,
+            };
+            self.last_click.set(Click {
+                origin: origin(layout),
+                count,
+            });
+        }
+        inner.process_event(event, layout)
+    }
+}
@@ -0,0 +1,137 @@
+use super::wrapper::{Wrap, Wrapped};
+use crate::widget::{Color, Style};
+use titik::{
+    crossterm::style,
+    stretch::{
+        geometry::Rect,
+        style::{Dimension, Style as LayoutStyle},
+    },
+    Buffer, Cell, Cmd, LayoutTree, Widget as Control,
+};
+
+/// Wraps a titik control and paints the cells it occupies with the colors of a style,
+/// the font of the style is left to the terminal.
+///
+/// The background is painted before the control is drawn, so the children of a container
+/// are drawn over it. The cells of a control without children are recolored after it is drawn,
+/// since titik controls draw their cells without colors.
+pub(crate) struct Painted {
+    style: Style,
+}
+
+impl Painted {
+    pub(crate) fn new<MSG>(inner: Box<dyn Control<MSG>>, style: Style) -> Wrapped<MSG, Self> {
+        Wrapped::new(inner, Painted { style })
+    }
+
+    fn has_border(&self) -> bool {
+        self.style.border_width.unwrap_or(0.0) > 0.0
+    }
+
+    fn draw_border(&self, buf: &mut Buffer, x: usize, y: usize, width: usize, height: usize) {
+        if width < 2 || height < 2 {
+            return;
+        }
+        let (top_start, top_end, bottom_start, bottom_end) =
+            if self.style.corner_radius.unwrap_or(0.0) > 0.0 {
+                ("╭", "╮", "╰", "╯")
+            } else {
+                ("┌", "┐", "└", "┘")
+            };
+        let color = self.style.border_color.or(self.style.color);
+        let mut set_cell = |x: usize, y: usize, symbol: &str| {
+            let mut cell = Cell::new(symbol);
+            if let Some(color) = color {
+                cell.color(terminal_color(color));
+            }
+            if let Some(background) = self.style.background {
+                cell.background(terminal_color(background));
+            }
+            buf.set_cell(x, y, cell);
+        };
+        for i in 1..width - 1 {
+            set_cell(x + i, y, "─");
+            set_cell(x + i, y + height - 1, "─");
+        }
+        for j in 1..height - 1 {
+            set_cell(x, y + j, "│");
+            set_cell(x + width - 1, y + j, "│");
+        }
+        set_cell(x, y, top_start);
+        set_cell(x + width - 1, y, top_end);
+        set_cell(x, y + height - 1, bottom_start);
+        set_cell(x + width - 1, y + height - 1, bottom_end);
+    }
+}
+
+impl<MSG> Wrap<MSG> for Painted {
+    const NAME: &'static str = "Painted";
+
+    /// the border takes a cell on each side
+    fn style(&self, inner: &dyn Control<MSG>) -> LayoutStyle {
+        let mut style = inner.style();
+        if self.has_border() {
+            style.border = Rect {
+                start: Dimension::Points(1.0),
+                end: Dimension::Points(1.0),
+                top: Dimension::Points(1.0),
+                bottom: Dimension::Points(1.0),
+            };
+        }
+        style
+    }
+
+    fn draw(
+        &self,
+        inner: &dyn Control<MSG>,
+        buf: &mut Buffer,
+        layout_tree: &LayoutTree,
+    ) -> Vec<Cmd> {
+        let layout = layout_tree.layout;
+        let loc_x = layout.location.x.round() as usize;
+        let loc_y = layout.location.y.round() as usize;
+        let width = layout.size.width.round() as usize;
+        let height = layout.size.height.round() as usize;
+        let color = self.style.color.map(terminal_color);
+        let background = self.style.background.map(terminal_color);
+
+        if let Some(background) = background {
+            for j in 0..height {
+                for i in 0..width {
+                    let mut cell = Cell::new(" ");
+                    cell.background(background);
+                    buf.set_cell(loc_x + i, loc_y + j, cell);
+                }
+            }
+        }
+        let cmds = inner.draw(buf, layout_tree);
+        // the children of a container are painted by their own wrapper
+        if inner.children().is_none() {
+            for j in 0..height {
+                for i in 0..width {
+                    if let Some(cell) = buf.get_cell_mut(loc_x + i, loc_y + j) {
+                        if let Some(color) = color {
+                            cell.color(color);
+                        }
+                        if let Some(background) = background {
+                            cell.background(background);
+                        }
+                    }
+                }
+            }
+        }
+        if self.has_border() {
+            self.draw_border(buf, loc_x, loc_y, width, height);
+        }
+        cmds
+    }
+}
+
+/// the terminal is assumed to support true colors, the alpha is ignored
+fn terminal_color(color: Color) -> style::Color {
+    style::Color::Rgb {
+        r: color.red,
+        g: color.green,
+        b: color.blue,
+    }
+}
@@ -0,0 +1,109 @@
+use super::wrapper::{Wrap, Wrapped};
+use titik::{
+    crossterm::{
+        event::{Event, KeyCode, MouseEvent},
+        style::Attribute,
+    },
+    stretch::result::Layout,
+    Buffer, Cmd, LayoutTree, Widget as Control,
+};
+
+/// Wraps a disabled or read only titik control, titik controls have no such state.
+///
+/// A disabled control is drawn dimmed, it reports itself as not focusable so it is
+/// passed over when the focus moves with tab, and it ignores the events. A read only control can still take
+/// the focus, be scrolled and have its cursor moved, only the events which would change
+/// its value are not given to it.
+pub(crate) struct Restricted {
+    disabled: bool,
+    /// whether a click changes the value of the control, as for checkboxes and radios
+    toggles: bool,
+}
+
+impl Restricted {
+    pub(crate) fn disabled<MSG>(inner: Box<dyn Control<MSG>>) -> Wrapped<MSG, Self> {
+        Wrapped::new(
+            inner,
+            Restricted {
+                disabled: true,
+                toggles: false,
+            },
+        )
+    }
+
+    pub(crate) fn read_only<MSG>(
+        inner: Box<dyn Control<MSG>>,
+        toggles: bool,
+    ) -> Wrapped<MSG, Self> {
+        Wrapped::new(
+            inner,
+            Restricted {
+                disabled: false,
+                toggles,
+            },
+        )
+    }
+
+    /// the typed characters, the deletions and the new lines change a text,
+    /// space and the clicks change the state of a toggle
+    fn changes_value(&self, event: &Event) -> bool {
+        match event {
+            Event::Key(key_event) => match key_event.code {
+                KeyCode::Char(_) | KeyCode::Backspace | KeyCode::Delete | KeyCode::Enter => true,
+                _ => false,
+            },
+            Event::Mouse(MouseEvent::Down(..)) | Event::Mouse(MouseEvent::Up(..)) => self.toggles,
+            _ => false,
+        }
+    }
+}
+
+impl<MSG> Wrap<MSG> for Restricted {
+    const NAME: &'static str = "Restricted";
+
+    fn draw(
+        &self,
+        inner: &dyn Control<MSG>,
+        buf: &mut Buffer,
+        layout_tree: &LayoutTree,
+    ) -> Vec<Cmd> {
+        let cmds = inner.draw(buf, layout_tree);
+        if self.disabled {
+            let layout = layout_tree.layout;
+            let loc_x = layout.location.x.round() as usize;
+            let loc_y = layout.location.y.round() as usize;
+            let width = layout.size.width.round() as usize;
+            let height = layout.size.height.round() as usize;
+            for j in 0..height {
+                for i in 0..width {
+                    if let Some(cell) = buf.get_cell_mut(loc_x + i, loc_y + j) {
+                        cell.attribute(Attribute::Dim);
+                    }
+                }
+            }
+        }
+        cmds
+    }
+
+    fn process_event(
+        &mut self,
+        inner: &mut dyn Control<MSG>,
+        event: Event,
+        layout: &Layout,
+    ) -> Vec<MSG> {
+        if self.disabled || self.changes_value(&event) {
+            vec![]
+        } else {
+            inner.process_event(event, layout)
+        }
+    }
+
+    fn set_focused(&mut self, inner: &mut dyn Control<MSG>, focused: bool) {
+        inner.set_focused(focused && !self.disabled)
+    }
+
+    /// the focus traversal of the renderer and set_focused_node pass over a disabled control
+    fn is_focusable(&self, inner: &dyn Control<MSG>) -> bool {
+        !self.disabled && inner.is_focusable()
+    }
+}
@@ -0,0 +1,99 @@
+use crate::widget::Orientation;
+use std::{any::Any, fmt};
+use titik::{
+    crossterm::event::Event,
+    stretch::{
+        geometry::Size,
+        result::Layout,
+        style::{Dimension, Style},
+    },
+    Buffer, Cell, Cmd, LayoutTree, Widget as Control,
+};
+
+/// A line drawn with box drawing characters across the space its container gives it
+pub(crate) struct Separator {
+    orientation: Orientation,
+}
+
+impl Separator {
+    pub(crate) fn new(orientation: Orientation) -> Self {
+        Separator { orientation }
+    }
+}
+
+impl fmt::Debug for Separator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Separator({:?})", self.orientation)
+    }
+}
+
+impl<MSG> Control<MSG> for Separator
+where
+    MSG: 'static,
+{
+    /// the line is 1 cell thick and stretches along the cross axis of its container
+    fn style(&self) -> Style {
+        let size = match self.orientation {
+            Orientation::Horizontal => Size {
+                width: Dimension::Auto,
+                height: Dimension::Points(1.0),
+            },
+            Orientation::Vertical => Size {
+                width: Dimension::Points(1.0),
+                height: Dimension::Auto,
+            },
+        };
+        Style {
+            size,
+            flex_shrink: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn add_child(&mut self, _child: Box<dyn Control<MSG>>) -> bool {
+        false
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        None
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        None
+    }
+
+    fn draw(&self, buf: &mut Buffer, layout_tree: &LayoutTree) -> Vec<Cmd> {
+        let layout = layout_tree.layout;
+        let loc_x = layout.location.x.round() as usize;
+        let loc_y = layout.location.y.round() as usize;
+        let width = layout.size.width.round() as usize;
+        let height = layout.size.height.round() as usize;
+        match self.orientation {
+            Orientation::Horizontal => {
+                for i in 0..width {
+                    buf.set_cell(loc_x + i, loc_y, Cell::new("─"));
+                }
+            }
+            Orientation::Vertical => {
+                for j in 0..height {
+                    buf.set_cell(loc_x, loc_y + j, Cell::new("│"));
+                }
+            }
+        }
+        vec![]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, _width: Option<f32>, _height: Option<f32>) {}
+
+    fn process_event(&mut self, _event: Event, _layout: &Layout) -> Vec<MSG> {
+        vec![]
+    }
+}
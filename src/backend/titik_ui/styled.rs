@@ -0,0 +1,32 @@
+use super::wrapper::{Wrap, Wrapped};
+use titik::{stretch::style::Style, Widget as Control};
+
+/// Wraps a titik control and alters the layout style the control reports,
+/// this is used for layout attributes which titik controls don't expose a setter for.
+pub(crate) struct Styled {
+    restyle: Box<dyn Fn(&mut Style)>,
+}
+
+impl Styled {
+    pub(crate) fn new<MSG, F>(inner: Box<dyn Control<MSG>>, restyle: F) -> Wrapped<MSG, Self>
+    where
+        F: Fn(&mut Style) + 'static,
+    {
+        Wrapped::new(
+            inner,
+            Styled {
+                restyle: Box::new(restyle),
+            },
+        )
+    }
+}
+
+impl<MSG> Wrap<MSG> for Styled {
+    const NAME: &'static str = "Styled";
+
+    fn style(&self, inner: &dyn Control<MSG>) -> Style {
+        let mut style = inner.style();
+        (self.restyle)(&mut style);
+        style
+    }
+}
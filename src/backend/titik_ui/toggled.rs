@@ -0,0 +1,54 @@
+use super::wrapper::{Wrap, Wrapped};
+use sauron_vdom::Callback;
+use titik::{
+    crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent},
+    stretch::result::Layout,
+    Widget as Control,
+};
+
+/// Wraps a titik checkbox or radio and emits its toggle listeners when it is clicked
+/// or when space is pressed, titik checkboxes and radios have no listeners of their own.
+///
+/// The events are given to the wrapped control too, so it shows the new state
+/// until the next view.
+pub(crate) struct Toggled<MSG> {
+    on_toggle: Vec<Callback<Event, MSG>>,
+}
+
+impl<MSG> Toggled<MSG> {
+    pub(crate) fn new(
+        inner: Box<dyn Control<MSG>>,
+        on_toggle: Vec<Callback<Event, MSG>>,
+    ) -> Wrapped<MSG, Self> {
+        Wrapped::new(inner, Toggled { on_toggle })
+    }
+
+    fn emit_toggle(&self, event: Event) -> Vec<MSG> {
+        self.on_toggle
+            .iter()
+            .map(|cb| cb.emit(event.clone()))
+            .collect()
+    }
+}
+
+impl<MSG> Wrap<MSG> for Toggled<MSG> {
+    const NAME: &'static str = "Toggled";
+
+    fn process_event(
+        &mut self,
+        inner: &mut dyn Control<MSG>,
+        event: Event,
+        layout: &Layout,
+    ) -> Vec<MSG> {
+        let mut msgs = inner.process_event(event.clone(), layout);
+        match event {
+            Event::Mouse(MouseEvent::Down(..))
+            | Event::Key(KeyEvent {
+                code: KeyCode::Char(' '),
+                ..
+            }) => msgs.extend(self.emit_toggle(event)),
+            _ => (),
+        }
+        msgs
+    }
+}
@@ -0,0 +1,116 @@
+use std::{any::Any, fmt};
+use titik::{
+    crossterm::event::Event,
+    stretch::{result::Layout, style::Style},
+    Buffer, Cmd, LayoutTree, Widget as Control,
+};
+
+/// What a wrapper adds to the titik control it wraps. Each method is given the wrapped
+/// control and delegates to it by default, so a wrapper only overrides what it changes.
+pub(crate) trait Wrap<MSG> {
+    /// the name of the wrapper when the control is debug printed
+    const NAME: &'static str;
+
+    fn style(&self, inner: &dyn Control<MSG>) -> Style {
+        inner.style()
+    }
+
+    fn draw(
+        &self,
+        inner: &dyn Control<MSG>,
+        buf: &mut Buffer,
+        layout_tree: &LayoutTree,
+    ) -> Vec<Cmd> {
+        inner.draw(buf, layout_tree)
+    }
+
+    fn process_event(
+        &mut self,
+        inner: &mut dyn Control<MSG>,
+        event: Event,
+        layout: &Layout,
+    ) -> Vec<MSG> {
+        inner.process_event(event, layout)
+    }
+
+    fn set_focused(&mut self, inner: &mut dyn Control<MSG>, focused: bool) {
+        inner.set_focused(focused)
+    }
+
+    fn is_focusable(&self, inner: &dyn Control<MSG>) -> bool {
+        inner.is_focusable()
+    }
+}
+
+/// A titik control with a wrapper around it. Everything the wrapper doesn't override is
+/// delegated to the control, so the wrapper is invisible in the layout tree and downcasting
+/// reaches the control, the same as when it is not wrapped.
+pub(crate) struct Wrapped<MSG, W> {
+    inner: Box<dyn Control<MSG>>,
+    wrapper: W,
+}
+
+impl<MSG, W> Wrapped<MSG, W> {
+    pub(crate) fn new(inner: Box<dyn Control<MSG>>, wrapper: W) -> Self {
+        Wrapped { inner, wrapper }
+    }
+}
+
+impl<MSG, W> fmt::Debug for Wrapped<MSG, W>
+where
+    W: Wrap<MSG>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}({:?})", W::NAME, self.inner)
+    }
+}
+
+impl<MSG, W> Control<MSG> for Wrapped<MSG, W>
+where
+    MSG: 'static,
+    W: Wrap<MSG> + 'static,
+{
+    fn style(&self) -> Style {
+        self.wrapper.style(&*self.inner)
+    }
+
+    fn add_child(&mut self, child: Box<dyn Control<MSG>>) -> bool {
+        self.inner.add_child(child)
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        self.inner.children()
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        self.inner.children_mut()
+    }
+
+    fn draw(&self, buf: &mut Buffer, layout_tree: &LayoutTree) -> Vec<Cmd> {
+        self.wrapper.draw(&*self.inner, buf, layout_tree)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self.inner.as_any()
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self.inner.as_any_mut()
+    }
+
+    fn set_size(&mut self, width: Option<f32>, height: Option<f32>) {
+        self.inner.set_size(width, height)
+    }
+
+    fn process_event(&mut self, event: Event, layout: &Layout) -> Vec<MSG> {
+        self.wrapper.process_event(&mut *self.inner, event, layout)
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.wrapper.set_focused(&mut *self.inner, focused)
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.wrapper.is_focusable(&*self.inner)
+    }
+}
@@ -4,7 +4,11 @@
 //#![feature(bindings_after_at)]
 
 pub mod event {
-    pub use sauron_vdom::{builder::on, event::InputEvent, Event};
+    pub use sauron_vdom::{
+        builder::on,
+        event::{InputEvent, KeyEvent, MouseButton, MouseEvent},
+        Event,
+    };
 }
 use std::fmt;
 use widget::attribute::AttribKey;
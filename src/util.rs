@@ -1,10 +1,161 @@
-use image::ImageFormat;
+use crate::widget::Fit;
+use image::{imageops::FilterType, DynamicImage, GenericImageView, ImageFormat, ImageOutputFormat};
+use std::{borrow::Cow, cell::RefCell, collections::HashMap, fs, path::PathBuf, rc::Rc};
 
+thread_local! {
+    static SOURCE_CACHE: RefCell<HashMap<PathBuf, Rc<Vec<u8>>>> = RefCell::new(HashMap::new());
+}
+
+/// the mime type of the image data, None when the format is not recognized
 pub(crate) fn image_mime_type(bytes: &[u8]) -> Option<&'static str> {
-    let img_format = image::guess_format(bytes).expect("must have an image format");
+    let img_format = image::guess_format(bytes).ok()?;
+    format_mime_type(img_format)
+}
+
+fn format_mime_type(img_format: ImageFormat) -> Option<&'static str> {
     match img_format {
         ImageFormat::Png => Some("image/png"),
         ImageFormat::Jpeg => Some("image/jpeg"),
+        ImageFormat::Gif => Some("image/gif"),
+        ImageFormat::WebP => Some("image/webp"),
+        ImageFormat::Tiff => Some("image/tiff"),
+        ImageFormat::Bmp => Some("image/bmp"),
+        ImageFormat::Ico => Some("image/x-icon"),
         _ => None,
     }
 }
+
+/// The image data in a format the backend can display and its mime type,
+/// data in any other format the image crate decodes is converted to PNG.
+pub(crate) fn displayable_image<'a>(
+    bytes: &'a [u8],
+    supported: &[ImageFormat],
+) -> Option<(Cow<'a, [u8]>, &'static str)> {
+    let img_format = match image::guess_format(bytes) {
+        Ok(img_format) => img_format,
+        Err(e) => {
+            log::error!("unrecognized image data: {}", e);
+            return None;
+        }
+    };
+    if supported.contains(&img_format) {
+        if let Some(mime) = format_mime_type(img_format) {
+            return Some((Cow::Borrowed(bytes), mime));
+        }
+    }
+    let image = decode_image(bytes)?;
+    let mut png = vec![];
+    if let Err(e) = image.write_to(&mut png, ImageOutputFormat::Png) {
+        log::error!("unable to convert image to png: {}", e);
+        return None;
+    }
+    Some((Cow::Owned(png), "image/png"))
+}
+
+/// decode the image data, the error is logged when it can't be decoded
+pub(crate) fn decode_image(bytes: &[u8]) -> Option<DynamicImage> {
+    match image::load_from_memory(bytes) {
+        Ok(image) => Some(image),
+        Err(e) => {
+            log::error!("unable to decode image: {}", e);
+            None
+        }
+    }
+}
+
+/// The box an image is shown in, a missing side is derived from the aspect ratio of the image
+/// and the image size is used when both are missing
+pub(crate) fn box_size(
+    width: Option<f64>,
+    height: Option<f64>,
+    (image_width, image_height): (u32, u32),
+) -> (u32, u32) {
+    let aspect_ratio = image_width as f64 / image_height.max(1) as f64;
+    match (width, height) {
+        (Some(width), Some(height)) => (width as u32, height as u32),
+        (Some(width), None) => (width as u32, (width / aspect_ratio) as u32),
+        (None, Some(height)) => ((height * aspect_ratio) as u32, height as u32),
+        (None, None) => (image_width, image_height),
+    }
+}
+
+/// The size the image is scaled to when shown in a box with the fit mode.
+/// The scaled image overflows the box with Fit::Cover and Fit::None,
+/// see `visible_region` for the part that is shown.
+pub(crate) fn fit_size(
+    fit: Fit,
+    (image_width, image_height): (u32, u32),
+    (box_width, box_height): (u32, u32),
+) -> (u32, u32) {
+    if image_width == 0 || image_height == 0 {
+        return (image_width, image_height);
+    }
+    let width_ratio = box_width as f64 / image_width as f64;
+    let height_ratio = box_height as f64 / image_height as f64;
+    let scale = |ratio: f64| {
+        (
+            ((image_width as f64 * ratio).round() as u32).max(1),
+            ((image_height as f64 * ratio).round() as u32).max(1),
+        )
+    };
+    match fit {
+        Fit::Contain => scale(width_ratio.min(height_ratio)),
+        Fit::Cover => scale(width_ratio.max(height_ratio)),
+        Fit::Fill => (box_width.max(1), box_height.max(1)),
+        Fit::None => (image_width, image_height),
+    }
+}
+
+/// The centered part of the scaled image that fits in the box: x, y, width and height
+pub(crate) fn visible_region(
+    (scaled_width, scaled_height): (u32, u32),
+    (box_width, box_height): (u32, u32),
+) -> (u32, u32, u32, u32) {
+    let width = scaled_width.min(box_width);
+    let height = scaled_height.min(box_height);
+    (
+        (scaled_width - width) / 2,
+        (scaled_height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// scale the decoded image to be shown in a box with the fit mode
+pub(crate) fn fit_image(image: &DynamicImage, fit: Fit, box_size: (u32, u32)) -> DynamicImage {
+    let scaled_size = fit_size(fit, image.dimensions(), box_size);
+    let scaled = if scaled_size == image.dimensions() {
+        image.clone()
+    } else {
+        image.resize_exact(scaled_size.0, scaled_size.1, FilterType::Triangle)
+    };
+    let (x, y, width, height) = visible_region(scaled_size, box_size);
+    scaled.crop_imm(x, y, width, height)
+}
+
+/// The file path of an image source, which is a path or a `file://` uri.
+/// A relative path is relative to the current directory.
+pub(crate) fn source_path(source: &str) -> PathBuf {
+    PathBuf::from(source.trim_start_matches("file://"))
+}
+
+/// read the content of an image source, a source is only read once and then shared
+pub(crate) fn source_bytes(source: &str) -> Option<Rc<Vec<u8>>> {
+    let path = source_path(source);
+    SOURCE_CACHE.with(|cache| {
+        if let Some(bytes) = cache.borrow().get(&path) {
+            return Some(Rc::clone(bytes));
+        }
+        match fs::read(&path) {
+            Ok(bytes) => {
+                let bytes = Rc::new(bytes);
+                cache.borrow_mut().insert(path, Rc::clone(&bytes));
+                Some(bytes)
+            }
+            Err(e) => {
+                log::error!("unable to read image {}: {}", path.display(), e);
+                None
+            }
+        }
+    })
+}
@@ -5,6 +5,17 @@ use std::fmt::Debug;
 
 pub mod attribute;
 mod control;
+pub(crate) mod date;
+pub(crate) mod focus;
+pub(crate) mod key;
+pub(crate) mod layout;
+pub(crate) mod mouse;
+pub(crate) mod state;
+pub(crate) mod style;
+pub(crate) mod window;
+
+pub use layout::{Align, Alignment, Fit, Insets, Orientation, Transition};
+pub use style::{Color, ColorScheme, Style, Theme};
 
 /// TODO: Each widget variant will need to have more details
 ///  such as attributes, that will be converted to their
@@ -15,18 +26,36 @@ mod control;
 /// different platforms
 #[derive(Debug, Clone, PartialEq)]
 pub enum Widget {
+    Application,
+    Window,
     Vbox,
     Hbox,
     Vpane,
     Hpane,
+    Grid,
+    Stack,
+    Pages,
+    Expander,
+    Toolbar,
+    StatusBar,
     Button,
     Paragraph,
     TextInput,
     Checkbox,
+    Switch,
     Radio,
     Image,
     Svg,
     TextArea,
+    DatePicker,
+    TimePicker,
+    Link,
+    Separator,
+    Space,
+    ToolButton,
+    ToolToggle,
+    ToolSeparator,
+    StatusSegment,
 }
 
 pub fn widget<MSG>(
@@ -37,6 +66,17 @@ pub fn widget<MSG>(
     element(widget, attrs, children)
 }
 
+/// an application with several top level windows, each window is identified
+/// by its key and is opened or closed as it appears or disappears in the view
+pub fn application<MSG>(attrs: Vec<Attribute<MSG>>, windows: Vec<Node<MSG>>) -> Node<MSG> {
+    widget(Widget::Application, attrs, windows)
+}
+
+/// the top level window, its children are laid out vertically
+pub fn window<MSG>(attrs: Vec<Attribute<MSG>>, children: Vec<Node<MSG>>) -> Node<MSG> {
+    widget(Widget::Window, attrs, children)
+}
+
 pub fn column<MSG>(attrs: Vec<Attribute<MSG>>, children: Vec<Node<MSG>>) -> Node<MSG> {
     widget(Widget::Vbox, attrs, children)
 }
@@ -52,6 +92,88 @@ pub fn hpane<MSG>(attrs: Vec<Attribute<MSG>>, children: Vec<Node<MSG>>) -> Node<
     widget(Widget::Hpane, attrs, children)
 }
 
+/// children are placed using the grid_row, grid_column, row_span and column_span
+/// attributes
+pub fn grid<MSG>(attrs: Vec<Attribute<MSG>>, children: Vec<Node<MSG>>) -> Node<MSG> {
+    widget(Widget::Grid, attrs, children)
+}
+
+/// children are layered on top of each other, the first child determines the size
+/// and the rest are placed using the halign and valign attributes
+pub fn stack<MSG>(attrs: Vec<Attribute<MSG>>, children: Vec<Node<MSG>>) -> Node<MSG> {
+    widget(Widget::Stack, attrs, children)
+}
+
+/// only the child whose name matches the visible_child attribute is shown
+pub fn pages<MSG>(attrs: Vec<Attribute<MSG>>, children: Vec<Node<MSG>>) -> Node<MSG> {
+    widget(Widget::Pages, attrs, children)
+}
+
+/// a section with a header showing the label attribute, the children are only shown
+/// while it is expanded
+pub fn expander<MSG>(attrs: Vec<Attribute<MSG>>, children: Vec<Node<MSG>>) -> Node<MSG> {
+    widget(Widget::Expander, attrs, children)
+}
+
+/// a calendar to pick the day in the value attribute, emits on_change
+pub fn date_picker<MSG>(attrs: Vec<Attribute<MSG>>) -> Node<MSG> {
+    widget(Widget::DatePicker, attrs, vec![])
+}
+
+/// hour and minute fields for the time in the value attribute, emits on_change
+pub fn time_picker<MSG>(attrs: Vec<Attribute<MSG>>) -> Node<MSG> {
+    widget(Widget::TimePicker, attrs, vec![])
+}
+
+/// a text link showing the label attribute, it either opens the uri attribute
+/// or emits on_click
+pub fn link<MSG>(attrs: Vec<Attribute<MSG>>) -> Node<MSG> {
+    widget(Widget::Link, attrs, vec![])
+}
+
+/// a horizontal or vertical line, following the orientation attribute
+pub fn separator<MSG>(attrs: Vec<Attribute<MSG>>) -> Node<MSG> {
+    widget(Widget::Separator, attrs, vec![])
+}
+
+/// an empty space of the width and height attributes,
+/// without them it grows to fill the space left in its container
+pub fn space<MSG>(attrs: Vec<Attribute<MSG>>) -> Node<MSG> {
+    widget(Widget::Space, attrs, vec![])
+}
+
+/// a row of tool_button, tool_toggle and tool_separator items
+pub fn toolbar<MSG>(attrs: Vec<Attribute<MSG>>, items: Vec<Node<MSG>>) -> Node<MSG> {
+    widget(Widget::Toolbar, attrs, items)
+}
+
+/// a toolbar item showing the icon and label attributes, emits on_click
+pub fn tool_button<MSG>(attrs: Vec<Attribute<MSG>>) -> Node<MSG> {
+    widget(Widget::ToolButton, attrs, vec![])
+}
+
+/// a toolbar item which stays pressed while its value is true, emits on_toggle
+pub fn tool_toggle<MSG>(attrs: Vec<Attribute<MSG>>) -> Node<MSG> {
+    widget(Widget::ToolToggle, attrs, vec![])
+}
+
+pub fn tool_separator<MSG>() -> Node<MSG> {
+    widget(Widget::ToolSeparator, vec![], vec![])
+}
+
+/// a line of status_segment at the bottom of a window
+pub fn status_bar<MSG>(attrs: Vec<Attribute<MSG>>, segments: Vec<Node<MSG>>) -> Node<MSG> {
+    widget(Widget::StatusBar, attrs, segments)
+}
+
+pub fn status_segment<MSG>(txt: &str) -> Node<MSG> {
+    widget(
+        Widget::StatusSegment,
+        vec![attribute::value(txt.to_string())],
+        vec![],
+    )
+}
+
 pub fn button<MSG>(attrs: Vec<Attribute<MSG>>) -> Node<MSG> {
     widget(Widget::Button, attrs, vec![])
 }
@@ -72,6 +194,12 @@ pub fn checkbox<MSG>(attrs: Vec<Attribute<MSG>>) -> Node<MSG> {
     widget(Widget::Checkbox, attrs, vec![])
 }
 
+pub fn switch<MSG>(attrs: Vec<Attribute<MSG>>) -> Node<MSG> {
+    widget(Widget::Switch, attrs, vec![])
+}
+
+/// The radios in the same row, column or other container form a group,
+/// checking one of them unchecks the others.
 pub fn radio<MSG>(attrs: Vec<Attribute<MSG>>) -> Node<MSG> {
     widget(Widget::Radio, attrs, vec![])
 }
@@ -8,19 +8,125 @@ use std::fmt;
 pub enum AttribKey {
     /// String, used in text_input
     Value,
-    /// String, used in button, label, checkbox, radio
+    /// String, used in button, label, checkbox, radio, expander
     Label,
     /// bool, used in checkbox, radio
     Checked,
-    /// Alignment Enum, used in hbox and vbox
+    /// Alignment Enum, placement of the children along a row or a column
     Alignment,
+    /// Align Enum, placement of the children across a row or a column
+    CrossAlignment,
+    /// f64, the space between the children of a row or a column
+    Spacing,
+    /// Insets, the space inside the edges of a row or a column
+    Padding,
+    /// Insets, the space outside the edges of a row or a column
+    Margin,
     ClickEvent,
     InputEvent,
+    /// emitted with a MouseEvent, used in any widget
+    MouseDownEvent,
+    /// emitted with a MouseEvent, used in any widget
+    MouseUpEvent,
+    /// emitted with a MouseEvent, used in any widget
+    MouseMoveEvent,
+    /// emitted with a MouseEvent, used in any widget
+    DoubleClickEvent,
+    /// emitted with a KeyEvent when a key is pressed, used in any widget
+    KeyDownEvent,
+    /// emitted with a KeyEvent when a key is released, used in any widget
+    KeyUpEvent,
+    /// emitted with the value of the widget when it takes the focus,
+    /// used in the interactive widgets
+    FocusEvent,
+    /// emitted with the value of the widget when it loses the focus,
+    /// used in the interactive widgets
+    BlurEvent,
+    /// emitted with the new boolean state, used in switch, expander, tool_toggle,
+    /// checkbox and radio
+    ToggleEvent,
+    /// emitted with the new value once it is complete, used in date_picker and time_picker
+    ChangeEvent,
+    /// used in window
+    CloseEvent,
+    /// emitted with the light or dark preference of the platform, used in window
+    ColorSchemeEvent,
+    /// Fit Enum, used in image and svg
+    Fit,
+    /// bool, masks the characters of a text_input
+    Password,
+    /// bool, shows a toggle to reveal the masked characters of a password text_input
+    Reveal,
+    /// bool, whether the children of an expander are shown
+    Expanded,
+    /// bool, the widget takes the keyboard focus when this becomes true
+    Focused,
+    /// u32, the widget takes the keyboard focus whenever this changes
+    FocusRequest,
+    /// u32, the order of the widget when moving the focus with the tab key
+    TabIndex,
+    /// bool, the widget can not be used, used in button, text_input, textarea, checkbox, radio
+    Disabled,
+    /// bool, the value can not be changed by the user, used in text_input, textarea,
+    /// checkbox, radio
+    ReadOnly,
+    /// bool, whether the widget and its children are shown, used in most widgets
+    Visible,
     Key,
     /// data, used in image blobs and svg
     Data,
+    /// a file path or uri where the image or svg is loaded from
+    Source,
+    /// String, the uri a link opens
+    Uri,
     Height,
     Width,
+    /// u32, the row of a child in a grid, starts at 0
+    GridRow,
+    /// u32, the column of a child in a grid, starts at 0
+    GridColumn,
+    /// u32, the number of rows a child spans in a grid
+    RowSpan,
+    /// u32, the number of columns a child spans in a grid
+    ColumnSpan,
+    /// Align Enum, horizontal placement of a child in a stack
+    HorizontalAlign,
+    /// Align Enum, vertical placement of a child in a stack
+    VerticalAlign,
+    /// String, identifies a child in pages
+    Name,
+    /// String, the name of the child shown in pages
+    VisibleChild,
+    /// Transition Enum, used in pages
+    Transition,
+    /// Orientation Enum, used in separator
+    Orientation,
+    /// Style, the colors, font and border of a widget
+    Style,
+    /// Theme, the colors and font of the widgets of a window, used in window
+    Theme,
+    /// Theme, used in window instead of the theme when the color scheme is dark
+    DarkTheme,
+    /// String, used in window
+    Title,
+    MinWidth,
+    MinHeight,
+    MaxWidth,
+    MaxHeight,
+    /// bool, the child grows to take the space left in its row or column
+    Expand,
+    /// bool, a growing child is stretched over the space it takes
+    Fill,
+    /// f64, the share of the space left a child takes in its row or column
+    FlexGrow,
+    /// f64, the share of the missing space a child gives up in its row or column
+    FlexShrink,
+    /// bool, used in window
+    Resizable,
+    /// String, an icon name or an image file path, used in window
+    Icon,
+    /// String, a reverse domain name identifying the application, used in application and window
+    ApplicationId,
 }
 
 impl fmt::Display for AttribKey {
@@ -93,19 +199,158 @@ macro_rules! declare_event_attr {
 }
 
 declare_attr! {
-    /// value attribute, used in text_input, textarea
+    /// value attribute, used in text_input, textarea, switch, tool_toggle,
+    /// status_segment, date_picker (`YYYY-MM-DD`) and time_picker (`HH:MM`)
     value => Value;
     /// data attribute, used in image, svg
     data => Data;
-    /// label attribute, used in button, checkbox and radio
+    /// source attribute, a file path or `file://` uri used in image and svg instead of data
+    source => Source;
+    /// the uri opened when a link without an on_click listener is clicked
+    uri => Uri;
+    /// whether a checkbox or a radio is checked, the value attribute is used
+    /// when there is none
+    checked => Checked;
+    /// label attribute, used in button, checkbox, radio, expander, link and tool items
     label => Label;
-    /// height attribute, used in most widgets
+    /// height attribute, used in most widgets, it is the default height in window
+    /// and the fixed height of a space
     height => Height;
-    /// width attribute, used in most widgets
+    /// width attribute, used in most widgets, it is the default width in window
+    /// and the fixed width of a space
     width => Width;
+    /// the minimum width of a window or a widget
+    min_width => MinWidth;
+    /// the minimum height of a window or a widget
+    min_height => MinHeight;
+    /// the maximum width of a widget, not supported in gtk
+    max_width => MaxWidth;
+    /// the maximum height of a widget, not supported in gtk
+    max_height => MaxHeight;
+    /// grow the child to take the space left in its row or column, same as a flex_grow of 1
+    expand => Expand;
+    /// stretch a growing child over the space it takes, otherwise it keeps its size
+    /// and is centered in that space, true by default
+    fill => Fill;
+    /// the share of the space left in its row or column a child takes
+    flex_grow => FlexGrow;
+    /// the share of the missing space in its row or column a child gives up,
+    /// 1 by default, not supported in gtk
+    flex_shrink => FlexShrink;
+    /// title attribute, used in window
+    title => Title;
+    /// whether the user can resize the window
+    resizable => Resizable;
+    /// icon of the window, tool_button and tool_toggle: an icon name or a file path
+    icon => Icon;
+    /// the application id, used in application and window
+    application_id => ApplicationId;
+    /// how an image or svg is scaled to its width and height
+    fit => Fit;
+    /// mask the characters typed in a text_input and prevent copying them
+    password => Password;
+    /// show a toggle which reveals the characters of a password text_input
+    reveal => Reveal;
+    /// show the children of an expander
+    expanded => Expanded;
+    /// give the keyboard focus to the widget, the focus is moved when the focused widget
+    /// changes between views
+    focused => Focused;
+    /// give the keyboard focus to the widget again each time this count changes, even when
+    /// the focused widget stays the same, such as after the user moved the focus elsewhere.
+    /// The widget is focused as with the focused attribute, which can be left out
+    focus_request => FocusRequest;
+    /// the order of the widget among its siblings when moving the focus with the tab key
+    tab_index => TabIndex;
+    /// the widget is dimmed, it can not take the focus and doesn't emit events.
+    /// Set it to false instead of leaving it out to enable the widget again
+    disabled => Disabled;
+    /// the user can focus the widget and select its text but not change its value.
+    /// Set it to false instead of leaving it out to allow changes again
+    read_only => ReadOnly;
+    /// hide the widget and its children without removing them from the view, so the
+    /// native widgets keep their state such as the text typed or the scroll position.
+    /// The terminal rebuilds its controls on every view and leaves the hidden ones out,
+    /// they keep no state there besides what the view gives them.
+    /// True by default
+    visible => Visible;
+    /// identifies a node among its siblings, used to match windows of an application
+    key => Key;
+    /// the row of a child in a grid
+    grid_row => GridRow;
+    /// the column of a child in a grid
+    grid_column => GridColumn;
+    /// the number of rows a child in a grid occupies
+    row_span => RowSpan;
+    /// the number of columns a child in a grid occupies
+    column_span => ColumnSpan;
+    /// how the children of a row or a column are placed along it, see Alignment
+    alignment => Alignment;
+    /// how the children of a row or a column are placed across it, Fill stretches them
+    cross_alignment => CrossAlignment;
+    /// the space in pixels between the children of a row or a column
+    spacing => Spacing;
+    /// the space inside the edges of a row or a column, a number or an Insets
+    padding => Padding;
+    /// the space outside the edges of a row or a column, a number or an Insets
+    margin => Margin;
+    /// horizontal alignment of a child in a stack
+    halign => HorizontalAlign;
+    /// vertical alignment of a child in a stack
+    valign => VerticalAlign;
+    /// name of a child in pages
+    name => Name;
+    /// the name of the child that pages shows
+    visible_child => VisibleChild;
+    /// the animation pages uses when switching the visible child
+    transition => Transition;
+    /// whether a separator is a horizontal or a vertical line
+    orientation => Orientation;
+    /// the colors, font and border of the widget, they take precedence over the theme
+    style => Style;
+    /// the colors and font of the widgets of a window, gtk applies the theme
+    /// to all the windows of the application
+    theme => Theme;
+    /// the theme used instead of the theme when the platform prefers a dark color scheme
+    dark_theme => DarkTheme;
 }
 
 declare_event_attr! {
     on_input => InputEvent;
     on_click => ClickEvent;
+    /// a mouse button is pressed over the widget, the event is a MouseEvent with the position
+    /// of the pointer in the widget and in the window, and the click count in detail
+    on_mouse_down => MouseDownEvent;
+    /// a mouse button is released over the widget
+    on_mouse_up => MouseUpEvent;
+    /// the pointer moves over the widget, the terminal only tells it while a button is held down
+    on_mouse_move => MouseMoveEvent;
+    /// the widget is clicked twice in a row, the click listeners are emitted for both clicks
+    on_double_click => DoubleClickEvent;
+    /// a key is pressed while the widget or one of its children has the focus,
+    /// the event is a KeyEvent. It is emitted again with repeat set while the key is held down,
+    /// except in the terminal which doesn't tell a held key apart so repeat is never set there
+    on_key_down => KeyDownEvent;
+    /// a key is released while the widget or one of its children has the focus,
+    /// the event is a KeyEvent. The terminal doesn't tell when keys are released
+    on_key_up => KeyUpEvent;
+    /// the widget takes the keyboard focus, the InputEvent value is the value of the widget:
+    /// the text of a text input or a textarea, "true" or "false" for a checkbox or a radio
+    /// and empty for the other widgets
+    on_focus => FocusEvent;
+    /// the widget loses the keyboard focus, the InputEvent value is the value the widget
+    /// is left with, so a field can be validated once the user is done with it
+    on_blur => BlurEvent;
+    /// toggle event, the InputEvent value is the new state: "true" or "false".
+    /// Used in switch, expander, tool_toggle, checkbox and radio
+    on_toggle => ToggleEvent;
+    /// change event, the InputEvent value is the new date or time in the ISO 8601 format
+    on_change => ChangeEvent;
+    /// the user requested to close the window, the window stays open
+    /// as long as it is in the view. A window without it is closed by the user right away,
+    /// closing the last window quits the application
+    on_close => CloseEvent;
+    /// the color scheme the platform prefers, the InputEvent value is `light` or `dark`.
+    /// It is emitted once the window is shown and when the preference changes
+    on_color_scheme => ColorSchemeEvent;
 }
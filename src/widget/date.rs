@@ -0,0 +1,263 @@
+use crate::{widget::attribute::find_value, AttribKey, Attribute};
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A day in the proleptic gregorian calendar, the value of a date_picker
+/// in the ISO 8601 format `YYYY-MM-DD`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Date {
+    pub(crate) year: i32,
+    /// 1 to 12
+    pub(crate) month: u32,
+    /// 1 to the number of days in the month
+    pub(crate) day: u32,
+}
+
+impl Date {
+    pub(crate) fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if month >= 1 && month <= 12 && day >= 1 && day <= days_in_month(year, month) {
+            Some(Date { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        Date::new(year, month, day)
+    }
+
+    /// the date in the value attribute, None when it is missing or not a valid date
+    pub(crate) fn find<MSG>(attrs: &Vec<Attribute<MSG>>) -> Option<Self>
+    where
+        MSG: 'static,
+    {
+        find_value(AttribKey::Value, attrs)
+            .map(|v| Date::parse(&v.to_string()))
+            .flatten()
+    }
+
+    /// the current date in UTC, this is not available in the browser
+    pub(crate) fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        Date::from_days((seconds / 86_400) as i64)
+    }
+
+    /// the day of the week, 0 is monday and 6 is sunday
+    pub(crate) fn weekday(&self) -> u32 {
+        // 1970-01-01 was a thursday
+        (self.to_days() + 3).rem_euclid(7) as u32
+    }
+
+    pub(crate) fn add_days(&self, days: i64) -> Self {
+        Date::from_days(self.to_days() + days)
+    }
+
+    /// move by whole months, the day is clamped to the length of the new month
+    pub(crate) fn add_months(&self, months: i32) -> Self {
+        let month_index = self.year * 12 + self.month as i32 - 1 + months;
+        let year = month_index.div_euclid(12);
+        let month = month_index.rem_euclid(12) as u32 + 1;
+        Date {
+            year,
+            month,
+            day: self.day.min(days_in_month(year, month)),
+        }
+    }
+
+    pub(crate) fn first_of_month(&self) -> Self {
+        Date { day: 1, ..*self }
+    }
+
+    /// days since 1970-01-01, using the algorithm from
+    /// http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    fn to_days(&self) -> i64 {
+        let year = if self.month <= 2 {
+            self.year as i64 - 1
+        } else {
+            self.year as i64
+        };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year =
+            (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// the inverse of to_days, see
+    /// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    fn from_days(days: i64) -> Self {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        } as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        Date {
+            year: year as i32,
+            month,
+            day,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+pub(crate) fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub(crate) fn month_name(month: u32) -> &'static str {
+    const MONTHS: [&str; 12] = [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+    MONTHS[(month as usize - 1) % 12]
+}
+
+/// A time of the day, the value of a time_picker in the ISO 8601 format `HH:MM`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub(crate) struct Time {
+    /// 0 to 23
+    pub(crate) hour: u32,
+    /// 0 to 59
+    pub(crate) minute: u32,
+}
+
+impl Time {
+    pub(crate) fn new(hour: u32, minute: u32) -> Option<Self> {
+        if hour < 24 && minute < 60 {
+            Some(Time { hour, minute })
+        } else {
+            None
+        }
+    }
+
+    /// the seconds of `HH:MM:SS` are accepted and dropped
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().splitn(3, ':');
+        let hour = parts.next()?.parse().ok()?;
+        let minute = parts.next()?.parse().ok()?;
+        Time::new(hour, minute)
+    }
+
+    /// the time in the value attribute, None when it is missing or not a valid time
+    pub(crate) fn find<MSG>(attrs: &Vec<Attribute<MSG>>) -> Option<Self>
+    where
+        MSG: 'static,
+    {
+        find_value(AttribKey::Value, attrs)
+            .map(|v| Time::parse(&v.to_string()))
+            .flatten()
+    }
+
+    /// move by minutes, wrapping around midnight
+    pub(crate) fn add_minutes(&self, minutes: i32) -> Self {
+        let total = (self.hour * 60 + self.minute) as i32 + minutes;
+        let total = total.rem_euclid(24 * 60) as u32;
+        Time {
+            hour: total / 60,
+            minute: total % 60,
+        }
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_round_trip() {
+        let date = Date::new(2020, 2, 29).expect("must be a date");
+        assert_eq!(date.to_string(), "2020-02-29");
+        assert_eq!(Date::parse(&date.to_string()), Some(date));
+        assert_eq!(Date::parse(" 2020-2-9 "), Date::new(2020, 2, 9));
+    }
+
+    #[test]
+    fn invalid_dates() {
+        assert_eq!(Date::parse("2019-02-29"), None);
+        assert_eq!(Date::parse("2020-13-01"), None);
+        assert_eq!(Date::parse("2020-04-31"), None);
+        assert_eq!(Date::parse("2020-04"), None);
+        assert_eq!(Date::parse("yesterday"), None);
+    }
+
+    #[test]
+    fn days_round_trip() {
+        assert_eq!(
+            Date::from_days(0),
+            Date::new(1970, 1, 1).expect("must be a date")
+        );
+        for days in (-800_000..800_000).step_by(997) {
+            assert_eq!(Date::from_days(days).to_days(), days);
+        }
+        // 2020-01-01 was a wednesday
+        assert_eq!(Date::new(2020, 1, 1).map(|date| date.weekday()), Some(2));
+    }
+
+    #[test]
+    fn add_months_clamps_the_day() {
+        let date = Date::new(2020, 1, 31).expect("must be a date");
+        assert_eq!(Some(date.add_months(1)), Date::new(2020, 2, 29));
+        assert_eq!(Some(date.add_months(11)), Date::new(2020, 12, 31));
+        assert_eq!(Some(date.add_months(-13)), Date::new(2018, 12, 31));
+    }
+
+    #[test]
+    fn time_round_trip() {
+        let time = Time::new(9, 5).expect("must be a time");
+        assert_eq!(time.to_string(), "09:05");
+        assert_eq!(Time::parse(&time.to_string()), Some(time));
+        assert_eq!(Time::parse("23:59:30"), Time::new(23, 59));
+        assert_eq!(Time::parse("24:00"), None);
+        assert_eq!(Some(time.add_minutes(-600)), Time::new(23, 5));
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{widget::attribute::find_value, AttribKey, Attribute, Node};
+
+/// whether the node asks for the keyboard focus,
+/// a node with a focus request asks for it without the focused attribute
+pub(crate) fn is_focused<MSG>(attrs: &Vec<Attribute<MSG>>) -> bool
+where
+    MSG: 'static,
+{
+    find_value(AttribKey::Focused, attrs)
+        .map(|v| v.as_bool())
+        .flatten()
+        .unwrap_or(false)
+        || focus_request(attrs).is_some()
+}
+
+/// the count of the times the node asked for the focus
+fn focus_request<MSG>(attrs: &Vec<Attribute<MSG>>) -> Option<u32>
+where
+    MSG: 'static,
+{
+    find_value(AttribKey::FocusRequest, attrs)
+        .map(|v| v.as_f64())
+        .flatten()
+        .map(|v| v.max(0.0) as u32)
+}
+
+/// the position of the node when moving the focus with the tab key,
+/// nodes without a tab index come after the ones that have it
+pub(crate) fn tab_index<MSG>(attrs: &Vec<Attribute<MSG>>) -> Option<u32>
+where
+    MSG: 'static,
+{
+    find_value(AttribKey::TabIndex, attrs)
+        .map(|v| v.as_f64())
+        .flatten()
+        .map(|v| v.max(0.0) as u32)
+}
+
+/// The first node of a view that asks for the focus. The focus is only moved when this
+/// changes, so the user can move the focus elsewhere while the view keeps declaring the
+/// same focused node, and the view moves it back to that node with a new focus request.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FocusTarget {
+    /// the child indexes leading from the root to the node
+    path: Vec<usize>,
+    request: u32,
+}
+
+pub(crate) fn focus_target<MSG>(node: &Node<MSG>) -> Option<FocusTarget>
+where
+    MSG: 'static,
+{
+    let element = node.as_element_ref()?;
+    if is_focused(&element.attrs) {
+        return Some(FocusTarget {
+            path: vec![],
+            request: focus_request(&element.attrs).unwrap_or(0),
+        });
+    }
+    element
+        .children
+        .iter()
+        .enumerate()
+        .find_map(|(index, child)| {
+            focus_target(child).map(|mut target| {
+                target.path.insert(0, index);
+                target
+            })
+        })
+}
@@ -0,0 +1,147 @@
+use sauron_vdom::{
+    event::{KeyEvent, Modifier},
+    Event,
+};
+
+/// The modifier keys held down while a key is pressed or the mouse is used
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct Modifiers {
+    pub(crate) ctrl: bool,
+    pub(crate) shift: bool,
+    pub(crate) alt: bool,
+    /// the command key on mac and the super or windows key elsewhere
+    pub(crate) meta: bool,
+}
+
+impl From<Modifiers> for Modifier {
+    fn from(modifiers: Modifiers) -> Modifier {
+        Modifier {
+            ctrl_key: modifiers.ctrl,
+            shift_key: modifiers.shift,
+            alt_key: modifiers.alt,
+            meta_key: modifiers.meta,
+        }
+    }
+}
+
+/// The key event given to the on_key_down and on_key_up listeners.
+///
+/// Every backend names the key the way the `key` of a web KeyboardEvent does:
+/// the character typed, such as `a` or `A` when shift is held down, or a name such as
+/// `Enter`, `Escape`, `Tab`, `Backspace`, `Delete`, `ArrowUp` or `F1`.
+/// The physical key is the `code` of a web KeyboardEvent, such as `KeyA`, `Digit1`,
+/// `ShiftLeft` or `Numpad0`, and `Unidentified` when the backend can't tell it.
+/// The location is derived from it.
+pub(crate) fn key_event(key: String, code: String, modifiers: Modifiers, repeat: bool) -> Event {
+    let location = location(&code);
+    Event::KeyEvent(KeyEvent {
+        key,
+        code,
+        modifier: modifiers.into(),
+        repeat,
+        location,
+    })
+}
+
+/// The location of a physical key, numbered the way the `location` of a web KeyboardEvent
+/// is: 1 and 2 for the left and right modifier keys, 3 for the numpad and 0 for the rest.
+fn location(code: &str) -> u32 {
+    let is_modifier = ["Control", "Shift", "Alt", "Meta"]
+        .iter()
+        .any(|modifier| code.starts_with(modifier));
+    if code.starts_with("Numpad") {
+        3
+    } else if is_modifier && code.ends_with("Left") {
+        1
+    } else if is_modifier && code.ends_with("Right") {
+        2
+    } else {
+        0
+    }
+}
+
+/// The physical key of a character typed on a US keyboard,
+/// for the backends which are only told the character.
+pub(crate) fn us_layout_code(ch: char) -> Option<String> {
+    if ch.is_ascii_alphabetic() {
+        return Some(format!("Key{}", ch.to_ascii_uppercase()));
+    }
+    if ch.is_ascii_digit() {
+        return Some(format!("Digit{}", ch));
+    }
+    // the characters typed with shift on the digits, from 1 to 0
+    if let Some(index) = "!@#$%^&*()".find(ch) {
+        return Some(format!("Digit{}", (index + 1) % 10));
+    }
+    let code = match ch {
+        ' ' => "Space",
+        '-' | '_' => "Minus",
+        '=' | '+' => "Equal",
+        '[' | '{' => "BracketLeft",
+        ']' | '}' => "BracketRight",
+        '\\' | '|' => "Backslash",
+        ';' | ':' => "Semicolon",
+        '\'' | '"' => "Quote",
+        '`' | '~' => "Backquote",
+        ',' | '<' => "Comma",
+        '.' | '>' => "Period",
+        '/' | '?' => "Slash",
+        _ => return None,
+    };
+    Some(code.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_event_fields() {
+        let modifiers = Modifiers {
+            shift: true,
+            ..Default::default()
+        };
+        let event = key_event(
+            "Shift".to_string(),
+            "ShiftRight".to_string(),
+            modifiers,
+            true,
+        );
+        if let Event::KeyEvent(event) = event {
+            assert_eq!(event.key, "Shift");
+            assert_eq!(event.code, "ShiftRight");
+            assert_eq!(event.location, 2);
+            assert!(event.repeat);
+            assert!(event.modifier.shift_key);
+            assert!(!event.modifier.ctrl_key);
+        } else {
+            panic!("expecting a key event");
+        }
+    }
+
+    #[test]
+    fn location_of_codes() {
+        assert_eq!(location("ControlLeft"), 1);
+        assert_eq!(location("MetaRight"), 2);
+        assert_eq!(location("Numpad1"), 3);
+        assert_eq!(location("NumpadEnter"), 3);
+        assert_eq!(location("KeyA"), 0);
+        assert_eq!(location("ArrowLeft"), 0);
+        assert_eq!(location("Unidentified"), 0);
+    }
+
+    #[test]
+    fn us_layout_codes() {
+        let code = |ch| us_layout_code(ch);
+        assert_eq!(code('a').as_deref(), Some("KeyA"));
+        assert_eq!(code('Q').as_deref(), Some("KeyQ"));
+        assert_eq!(code('7').as_deref(), Some("Digit7"));
+        assert_eq!(code('!').as_deref(), Some("Digit1"));
+        assert_eq!(code('(').as_deref(), Some("Digit9"));
+        assert_eq!(code(')').as_deref(), Some("Digit0"));
+        assert_eq!(code(' ').as_deref(), Some("Space"));
+        assert_eq!(code('?').as_deref(), Some("Slash"));
+        assert_eq!(code('"').as_deref(), Some("Quote"));
+        assert_eq!(code('é'), None);
+    }
+}
@@ -0,0 +1,587 @@
+use crate::{widget::attribute::find_value, AttribKey, Attribute, Value};
+
+/// The cell a child occupies when placed inside a grid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct GridCell {
+    pub(crate) row: u32,
+    pub(crate) column: u32,
+    pub(crate) row_span: u32,
+    pub(crate) column_span: u32,
+}
+
+impl GridCell {
+    /// read the grid placement of a child from its attributes,
+    /// children that don't declare it are placed at the top left cell
+    pub(crate) fn from_attrs<MSG>(attrs: &Vec<Attribute<MSG>>) -> Self
+    where
+        MSG: 'static,
+    {
+        let find_u32 = |key: AttribKey| {
+            find_value(key, attrs)
+                .map(|v| v.as_f64())
+                .flatten()
+                .map(|v| v.max(0.0) as u32)
+        };
+        GridCell {
+            row: find_u32(AttribKey::GridRow).unwrap_or(0),
+            column: find_u32(AttribKey::GridColumn).unwrap_or(0),
+            row_span: find_u32(AttribKey::RowSpan).unwrap_or(1).max(1),
+            column_span: find_u32(AttribKey::ColumnSpan).unwrap_or(1).max(1),
+        }
+    }
+}
+
+/// Alignment of a child within the space its container gives it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Align {
+    /// stretch the child to occupy the whole space
+    Fill,
+    Start,
+    Center,
+    End,
+}
+
+impl Align {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Align::Fill => "fill",
+            Align::Start => "start",
+            Align::Center => "center",
+            Align::End => "end",
+        }
+    }
+
+    pub(crate) fn from_value(value: &Value) -> Option<Self> {
+        match &*value.to_string() {
+            "fill" => Some(Align::Fill),
+            "start" => Some(Align::Start),
+            "center" => Some(Align::Center),
+            "end" => Some(Align::End),
+            _ => None,
+        }
+    }
+
+    /// find the alignment declared by the attribute key, defaults to Fill
+    pub(crate) fn find<MSG>(key: AttribKey, attrs: &Vec<Attribute<MSG>>) -> Self
+    where
+        MSG: 'static,
+    {
+        find_value(key, attrs)
+            .map(Align::from_value)
+            .flatten()
+            .unwrap_or(Align::Fill)
+    }
+}
+
+impl From<Align> for Value {
+    fn from(align: Align) -> Value {
+        Value::from(align.as_str())
+    }
+}
+
+/// Placement of the children of a row or a column along its direction,
+/// the placement across its direction is an Align
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Alignment {
+    Start,
+    Center,
+    End,
+}
+
+impl Alignment {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Alignment::Start => "start",
+            Alignment::Center => "center",
+            Alignment::End => "end",
+        }
+    }
+
+    pub(crate) fn from_value(value: &Value) -> Option<Self> {
+        match &*value.to_string() {
+            "start" => Some(Alignment::Start),
+            "center" => Some(Alignment::Center),
+            "end" => Some(Alignment::End),
+            _ => None,
+        }
+    }
+
+    /// find the alignment of the children of a row or a column, defaults to Start
+    pub(crate) fn find<MSG>(attrs: &Vec<Attribute<MSG>>) -> Self
+    where
+        MSG: 'static,
+    {
+        find_value(AttribKey::Alignment, attrs)
+            .map(Alignment::from_value)
+            .flatten()
+            .unwrap_or(Alignment::Start)
+    }
+}
+
+impl From<Alignment> for Value {
+    fn from(alignment: Alignment) -> Value {
+        Value::from(alignment.as_str())
+    }
+}
+
+/// The space in pixels at each side of a widget, used for padding and margin.
+/// A single number can also be used where the same space is on all the sides.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Insets {
+    pub top: f64,
+    pub end: f64,
+    pub bottom: f64,
+    pub start: f64,
+}
+
+impl Insets {
+    pub fn uniform(size: f64) -> Self {
+        Insets {
+            top: size,
+            end: size,
+            bottom: size,
+            start: size,
+        }
+    }
+
+    pub fn symmetric(vertical: f64, horizontal: f64) -> Self {
+        Insets {
+            top: vertical,
+            end: horizontal,
+            bottom: vertical,
+            start: horizontal,
+        }
+    }
+
+    /// a number, or the sides in the order of css: `top end bottom start`
+    /// or `vertical horizontal`
+    pub(crate) fn from_value(value: &Value) -> Option<Self> {
+        if let Some(size) = value.as_f64() {
+            return Some(Insets::uniform(size));
+        }
+        let sides: Vec<f64> = value
+            .to_string()
+            .split_whitespace()
+            .map(|side| side.parse().ok())
+            .collect::<Option<_>>()?;
+        match sides[..] {
+            [size] => Some(Insets::uniform(size)),
+            [vertical, horizontal] => Some(Insets::symmetric(vertical, horizontal)),
+            [top, end, bottom, start] => Some(Insets {
+                top,
+                end,
+                bottom,
+                start,
+            }),
+            _ => None,
+        }
+    }
+
+    /// find the insets declared by the attribute key, defaults to no space
+    pub(crate) fn find<MSG>(key: AttribKey, attrs: &Vec<Attribute<MSG>>) -> Self
+    where
+        MSG: 'static,
+    {
+        find_value(key, attrs)
+            .map(Insets::from_value)
+            .flatten()
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        *self == Insets::default()
+    }
+}
+
+impl From<Insets> for Value {
+    fn from(insets: Insets) -> Value {
+        Value::from(format!(
+            "{} {} {} {}",
+            insets.top, insets.end, insets.bottom, insets.start
+        ))
+    }
+}
+
+/// The space in pixels between the children of a row or a column
+pub(crate) fn spacing<MSG>(attrs: &Vec<Attribute<MSG>>) -> f64
+where
+    MSG: 'static,
+{
+    find_value(AttribKey::Spacing, attrs)
+        .map(|v| v.as_f64())
+        .flatten()
+        .map(|v| v.max(0.0))
+        .unwrap_or(0.0)
+}
+
+/// How a child is sized along the direction of the row or the column it is in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Flex {
+    /// the share of the space left in the parent the child takes, 0 doesn't grow
+    pub(crate) grow: f64,
+    /// the share of the missing space the child gives up, 0 doesn't shrink
+    pub(crate) shrink: f64,
+    /// whether a growing child is stretched over the space it takes,
+    /// otherwise it keeps its size and is centered in it
+    pub(crate) fill: bool,
+}
+
+impl Flex {
+    /// expand is a grow factor of 1, flex_grow takes precedence over it
+    pub(crate) fn from_attrs<MSG>(attrs: &Vec<Attribute<MSG>>) -> Self
+    where
+        MSG: 'static,
+    {
+        let find_f64 = |key| {
+            find_value(key, attrs)
+                .map(|v| v.as_f64())
+                .flatten()
+                .map(|v| v.max(0.0))
+        };
+        let find_bool = |key| find_value(key, attrs).map(|v| v.as_bool()).flatten();
+        let expand = find_bool(AttribKey::Expand).unwrap_or(false);
+        Flex {
+            grow: find_f64(AttribKey::FlexGrow).unwrap_or(if expand { 1.0 } else { 0.0 }),
+            shrink: find_f64(AttribKey::FlexShrink).unwrap_or(1.0),
+            fill: find_bool(AttribKey::Fill).unwrap_or(true),
+        }
+    }
+
+    pub(crate) fn expands(&self) -> bool {
+        self.grow > 0.0
+    }
+
+    /// whether the attributes declare anything other than the defaults
+    pub(crate) fn is_declared<MSG>(attrs: &Vec<Attribute<MSG>>) -> bool
+    where
+        MSG: 'static,
+    {
+        [
+            AttribKey::Expand,
+            AttribKey::Fill,
+            AttribKey::FlexGrow,
+            AttribKey::FlexShrink,
+        ]
+        .iter()
+        .any(|key| find_value(key.clone(), attrs).is_some())
+    }
+}
+
+/// The minimum and maximum size in pixels of a widget
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct SizeConstraints {
+    pub(crate) min_width: Option<f64>,
+    pub(crate) min_height: Option<f64>,
+    pub(crate) max_width: Option<f64>,
+    pub(crate) max_height: Option<f64>,
+}
+
+impl SizeConstraints {
+    pub(crate) fn from_attrs<MSG>(attrs: &Vec<Attribute<MSG>>) -> Self
+    where
+        MSG: 'static,
+    {
+        let find_f64 = |key| {
+            find_value(key, attrs)
+                .map(|v| v.as_f64())
+                .flatten()
+                .map(|v| v.max(0.0))
+        };
+        SizeConstraints {
+            min_width: find_f64(AttribKey::MinWidth),
+            min_height: find_f64(AttribKey::MinHeight),
+            max_width: find_f64(AttribKey::MaxWidth),
+            max_height: find_f64(AttribKey::MaxHeight),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        *self == SizeConstraints::default()
+    }
+}
+
+/// The animation used when a stack of pages switches the child it shows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transition {
+    None,
+    Crossfade,
+    SlideLeft,
+    SlideRight,
+    SlideUp,
+    SlideDown,
+}
+
+impl Transition {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Transition::None => "none",
+            Transition::Crossfade => "crossfade",
+            Transition::SlideLeft => "slide_left",
+            Transition::SlideRight => "slide_right",
+            Transition::SlideUp => "slide_up",
+            Transition::SlideDown => "slide_down",
+        }
+    }
+
+    pub(crate) fn from_value(value: &Value) -> Option<Self> {
+        match &*value.to_string() {
+            "none" => Some(Transition::None),
+            "crossfade" => Some(Transition::Crossfade),
+            "slide_left" => Some(Transition::SlideLeft),
+            "slide_right" => Some(Transition::SlideRight),
+            "slide_up" => Some(Transition::SlideUp),
+            "slide_down" => Some(Transition::SlideDown),
+            _ => None,
+        }
+    }
+
+    /// find the transition of the pages, defaults to no transition
+    pub(crate) fn find<MSG>(attrs: &Vec<Attribute<MSG>>) -> Self
+    where
+        MSG: 'static,
+    {
+        find_value(AttribKey::Transition, attrs)
+            .map(Transition::from_value)
+            .flatten()
+            .unwrap_or(Transition::None)
+    }
+}
+
+impl From<Transition> for Value {
+    fn from(transition: Transition) -> Value {
+        Value::from(transition.as_str())
+    }
+}
+
+/// The direction of the line drawn by a separator
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+impl Orientation {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Orientation::Horizontal => "horizontal",
+            Orientation::Vertical => "vertical",
+        }
+    }
+
+    pub(crate) fn from_value(value: &Value) -> Option<Self> {
+        match &*value.to_string() {
+            "horizontal" => Some(Orientation::Horizontal),
+            "vertical" => Some(Orientation::Vertical),
+            _ => None,
+        }
+    }
+
+    /// find the orientation of a separator, defaults to Horizontal
+    pub(crate) fn find<MSG>(attrs: &Vec<Attribute<MSG>>) -> Self
+    where
+        MSG: 'static,
+    {
+        find_value(AttribKey::Orientation, attrs)
+            .map(Orientation::from_value)
+            .flatten()
+            .unwrap_or(Orientation::Horizontal)
+    }
+}
+
+impl From<Orientation> for Value {
+    fn from(orientation: Orientation) -> Value {
+        Value::from(orientation.as_str())
+    }
+}
+
+/// A space with a width or a height has a fixed size,
+/// otherwise it grows to fill the space left in its container
+pub(crate) fn is_flexible_space<MSG>(attrs: &Vec<Attribute<MSG>>) -> bool
+where
+    MSG: 'static,
+{
+    find_value(AttribKey::Width, attrs).is_none() && find_value(AttribKey::Height, attrs).is_none()
+}
+
+/// The name of a child in a stack of pages, children without a name
+/// are referred to by their index
+pub(crate) fn page_name<MSG>(index: usize, attrs: &Vec<Attribute<MSG>>) -> String
+where
+    MSG: 'static,
+{
+    find_value(AttribKey::Name, attrs)
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| index.to_string())
+}
+
+/// The name of the page that is shown, the first page is shown if unspecified
+pub(crate) fn visible_page<MSG>(attrs: &Vec<Attribute<MSG>>) -> String
+where
+    MSG: 'static,
+{
+    find_value(AttribKey::VisibleChild, attrs)
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| 0.to_string())
+}
+
+/// How an image is scaled to the box given by its width and height
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fit {
+    /// scale preserving the aspect ratio so the whole image is visible
+    Contain,
+    /// scale preserving the aspect ratio so the image covers the whole box,
+    /// the overflowing part is cropped
+    Cover,
+    /// stretch the image to the box
+    Fill,
+    /// keep the image size, the overflowing part is cropped
+    None,
+}
+
+impl Fit {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Fit::Contain => "contain",
+            Fit::Cover => "cover",
+            Fit::Fill => "fill",
+            Fit::None => "none",
+        }
+    }
+
+    pub(crate) fn from_value(value: &Value) -> Option<Self> {
+        match &*value.to_string() {
+            "contain" => Some(Fit::Contain),
+            "cover" => Some(Fit::Cover),
+            "fill" => Some(Fit::Fill),
+            "none" => Some(Fit::None),
+            _ => None,
+        }
+    }
+
+    /// find the fit of an image, defaults to Contain
+    pub(crate) fn find<MSG>(attrs: &Vec<Attribute<MSG>>) -> Self
+    where
+        MSG: 'static,
+    {
+        find_value(AttribKey::Fit, attrs)
+            .map(Fit::from_value)
+            .flatten()
+            .unwrap_or(Fit::Contain)
+    }
+}
+
+impl From<Fit> for Value {
+    fn from(fit: Fit) -> Value {
+        Value::from(fit.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::attribute::{
+        column_span, expand, fill, flex_grow, grid_column, grid_row, max_height, min_width,
+        row_span, transition,
+    };
+
+    #[test]
+    fn grid_cell_from_attrs() {
+        let attrs: Vec<Attribute<()>> = vec![grid_row(2), grid_column(1), column_span(3)];
+        assert_eq!(
+            GridCell::from_attrs(&attrs),
+            GridCell {
+                row: 2,
+                column: 1,
+                row_span: 1,
+                column_span: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn grid_cell_defaults_to_the_top_left_cell() {
+        let attrs: Vec<Attribute<()>> = vec![row_span(0)];
+        assert_eq!(
+            GridCell::from_attrs(&attrs),
+            GridCell {
+                row: 0,
+                column: 0,
+                row_span: 1,
+                column_span: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn insets_round_trip() {
+        let insets = Insets {
+            top: 1.0,
+            end: 2.5,
+            bottom: 3.0,
+            start: 4.0,
+        };
+        assert_eq!(Insets::from_value(&Value::from(insets)), Some(insets));
+    }
+
+    #[test]
+    fn insets_from_value() {
+        assert_eq!(
+            Insets::from_value(&Value::from(5.0)),
+            Some(Insets::uniform(5.0))
+        );
+        assert_eq!(
+            Insets::from_value(&Value::from("2 8")),
+            Some(Insets::symmetric(2.0, 8.0))
+        );
+        assert_eq!(Insets::from_value(&Value::from("1 2 3")), None);
+        assert_eq!(Insets::from_value(&Value::from("wide")), None);
+    }
+
+    #[test]
+    fn flex_from_attrs() {
+        let attrs: Vec<Attribute<()>> = vec![expand(true), fill(false)];
+        assert_eq!(
+            Flex::from_attrs(&attrs),
+            Flex {
+                grow: 1.0,
+                shrink: 1.0,
+                fill: false,
+            }
+        );
+        let attrs: Vec<Attribute<()>> = vec![expand(true), flex_grow(3.0)];
+        assert_eq!(Flex::from_attrs(&attrs).grow, 3.0);
+        assert!(Flex::is_declared(&attrs));
+        assert!(!Flex::is_declared::<()>(&vec![]));
+    }
+
+    #[test]
+    fn size_constraints_from_attrs() {
+        let attrs: Vec<Attribute<()>> = vec![min_width(100.0), max_height(-5.0)];
+        assert_eq!(
+            SizeConstraints::from_attrs(&attrs),
+            SizeConstraints {
+                min_width: Some(100.0),
+                max_height: Some(0.0),
+                ..Default::default()
+            }
+        );
+        assert!(SizeConstraints::from_attrs::<()>(&vec![]).is_empty());
+    }
+
+    #[test]
+    fn transition_round_trip() {
+        let transitions = [
+            Transition::None,
+            Transition::Crossfade,
+            Transition::SlideLeft,
+            Transition::SlideRight,
+            Transition::SlideUp,
+            Transition::SlideDown,
+        ];
+        for expected in transitions.iter() {
+            let attrs: Vec<Attribute<()>> = vec![transition(*expected)];
+            assert_eq!(Transition::find(&attrs), *expected);
+        }
+        assert_eq!(Transition::from_value(&Value::from("zoom")), None);
+    }
+}
@@ -0,0 +1,39 @@
+use crate::widget::key::Modifiers;
+use sauron_vdom::{
+    event::{Coordinate, MouseButton, MouseEvent},
+    Event,
+};
+
+/// The mouse event given to the click and the mouse listeners.
+///
+/// The type is named the way the web names it: `click`, `dblclick`, `mousedown`,
+/// `mouseup` or `mousemove`. The position in the widget is in offset_x and offset_y,
+/// the position in the window is in client_x and client_y.
+///
+/// The click count is in detail, as the web has it: the number of presses in a row
+/// of a press, a release or a click, 2 for a double click and 0 for a move
+/// or a click made with the keyboard.
+pub(crate) fn mouse_event(
+    r#type: &'static str,
+    widget_position: (i32, i32),
+    window_position: (i32, i32),
+    button: MouseButton,
+    modifiers: Modifiers,
+    click_count: i32,
+) -> Event {
+    Event::MouseEvent(MouseEvent {
+        r#type,
+        coordinate: Coordinate {
+            client_x: window_position.0,
+            client_y: window_position.1,
+            offset_x: widget_position.0,
+            offset_y: widget_position.1,
+            x: window_position.0,
+            y: window_position.1,
+            ..Default::default()
+        },
+        modifier: modifiers.into(),
+        buttons: button,
+        detail: click_count,
+    })
+}
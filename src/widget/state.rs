@@ -0,0 +1,47 @@
+use crate::{widget::attribute::find_value, AttribKey, Attribute};
+
+/// whether a checkbox or a radio is checked, from the checked attribute
+/// or the value attribute when there is none
+pub(crate) fn is_checked<MSG>(attrs: &Vec<Attribute<MSG>>) -> bool
+where
+    MSG: 'static,
+{
+    find_value(AttribKey::Checked, attrs)
+        .or_else(|| find_value(AttribKey::Value, attrs))
+        .map(|v| v.as_bool())
+        .flatten()
+        .unwrap_or(false)
+}
+
+/// whether the widget is disabled, a disabled widget can not be used
+pub(crate) fn is_disabled<MSG>(attrs: &Vec<Attribute<MSG>>) -> bool
+where
+    MSG: 'static,
+{
+    find_value(AttribKey::Disabled, attrs)
+        .map(|v| v.as_bool())
+        .flatten()
+        .unwrap_or(false)
+}
+
+/// whether the value of the widget can only be changed by the view
+pub(crate) fn is_read_only<MSG>(attrs: &Vec<Attribute<MSG>>) -> bool
+where
+    MSG: 'static,
+{
+    find_value(AttribKey::ReadOnly, attrs)
+        .map(|v| v.as_bool())
+        .flatten()
+        .unwrap_or(false)
+}
+
+/// whether the widget is shown, a hidden widget takes no space in the layout
+pub(crate) fn is_visible<MSG>(attrs: &Vec<Attribute<MSG>>) -> bool
+where
+    MSG: 'static,
+{
+    find_value(AttribKey::Visible, attrs)
+        .map(|v| v.as_bool())
+        .flatten()
+        .unwrap_or(true)
+}
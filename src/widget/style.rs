@@ -0,0 +1,506 @@
+use crate::{widget::attribute::find_value, AttribKey, Attribute, Value, Widget};
+use std::fmt;
+
+/// A color with an alpha channel, where an alpha of 255 is opaque
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    pub alpha: u8,
+}
+
+impl Color {
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Color::rgba(red, green, blue, 255)
+    }
+
+    pub fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Color {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    /// `#rgb`, `#rrggbb`, `#rrggbbaa` or the css `rgb(r, g, b)` and `rgba(r, g, b, a)`
+    /// where the alpha is from 0 to 1
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.starts_with('#') {
+            let hex = &value[1..];
+            let channel = |i: usize, len: usize| u8::from_str_radix(hex.get(i..i + len)?, 16).ok();
+            match hex.len() {
+                // each digit is repeated, #f80 is #ff8800
+                3 => Some(Color::rgb(
+                    channel(0, 1)? * 17,
+                    channel(1, 1)? * 17,
+                    channel(2, 1)? * 17,
+                )),
+                6 => Some(Color::rgb(channel(0, 2)?, channel(2, 2)?, channel(4, 2)?)),
+                8 => Some(Color::rgba(
+                    channel(0, 2)?,
+                    channel(2, 2)?,
+                    channel(4, 2)?,
+                    channel(6, 2)?,
+                )),
+                _ => None,
+            }
+        } else if value.starts_with("rgb") && value.ends_with(')') {
+            let arguments = &value[value.find('(')? + 1..value.len() - 1];
+            let channels: Vec<&str> = arguments.split(',').map(str::trim).collect();
+            let channel = |i: usize| channels[i].parse::<u8>().ok();
+            match channels.len() {
+                3 => Some(Color::rgb(channel(0)?, channel(1)?, channel(2)?)),
+                4 => {
+                    let alpha = channels[3].parse::<f64>().ok()?.max(0.0).min(1.0);
+                    Some(Color::rgba(
+                        channel(0)?,
+                        channel(1)?,
+                        channel(2)?,
+                        (alpha * 255.0).round() as u8,
+                    ))
+                }
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// the css notation of the color, which is also understood by gtk
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.alpha == 255 {
+            write!(f, "#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+        } else {
+            write!(
+                f,
+                "rgba({}, {}, {}, {:.3})",
+                self.red,
+                self.green,
+                self.blue,
+                self.alpha as f64 / 255.0
+            )
+        }
+    }
+}
+
+impl From<Color> for Value {
+    fn from(color: Color) -> Value {
+        Value::from(color.to_string())
+    }
+}
+
+/// The look of a widget, the parts which are not set are left to the theme
+/// and to the platform.
+///
+/// The color and the font are inherited by the children of the widget.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Style {
+    /// the color of the text
+    pub color: Option<Color>,
+    pub background: Option<Color>,
+    pub font_family: Option<String>,
+    /// in pixels
+    pub font_size: Option<f64>,
+    /// in pixels, the border is a solid line
+    pub border_width: Option<f64>,
+    pub border_color: Option<Color>,
+    /// the radius of the rounded corners in pixels
+    pub corner_radius: Option<f64>,
+}
+
+impl Style {
+    /// the css declarations of the style, gtk understands the same properties
+    pub(crate) fn declarations(&self) -> Vec<(&'static str, String)> {
+        let mut declarations = vec![];
+        if let Some(color) = self.color {
+            declarations.push(("color", color.to_string()));
+        }
+        if let Some(background) = self.background {
+            declarations.push(("background-color", background.to_string()));
+        }
+        if let Some(font_family) = &self.font_family {
+            declarations.push(("font-family", font_family.clone()));
+        }
+        if let Some(font_size) = self.font_size {
+            declarations.push(("font-size", format!("{}px", font_size)));
+        }
+        if let Some(border_width) = self.border_width {
+            declarations.push(("border-width", format!("{}px", border_width)));
+            declarations.push(("border-style", "solid".to_string()));
+        }
+        if let Some(border_color) = self.border_color {
+            declarations.push(("border-color", border_color.to_string()));
+        }
+        if let Some(corner_radius) = self.corner_radius {
+            declarations.push(("border-radius", format!("{}px", corner_radius)));
+        }
+        declarations
+    }
+
+    /// the declarations joined as the content of a css rule
+    pub(crate) fn to_css(&self) -> String {
+        self.declarations()
+            .into_iter()
+            .map(|(property, value)| format!("{}: {};", property, value))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// css declarations, the properties which are not part of a Style are ignored
+    pub(crate) fn from_value(value: &Value) -> Self {
+        let mut style = Style::default();
+        for (property, value) in parse_declarations(&value.to_string()) {
+            match &*property {
+                "color" => style.color = Color::parse(&value),
+                "background-color" | "background" => style.background = Color::parse(&value),
+                "font-family" => style.font_family = Some(value),
+                "font-size" => style.font_size = parse_pixels(&value),
+                "border-width" => style.border_width = parse_pixels(&value),
+                "border-color" => style.border_color = Color::parse(&value),
+                "border-radius" => style.corner_radius = parse_pixels(&value),
+                _ => (),
+            }
+        }
+        style
+    }
+
+    /// the style attribute of a widget, None when it has none
+    pub(crate) fn find<MSG>(attrs: &Vec<Attribute<MSG>>) -> Option<Self>
+    where
+        MSG: 'static,
+    {
+        find_value(AttribKey::Style, attrs).map(Style::from_value)
+    }
+
+    /// the properties set in other take precedence
+    pub(crate) fn merge(&self, other: &Style) -> Style {
+        Style {
+            color: other.color.or(self.color),
+            background: other.background.or(self.background),
+            font_family: other.font_family.clone().or(self.font_family.clone()),
+            font_size: other.font_size.or(self.font_size),
+            border_width: other.border_width.or(self.border_width),
+            border_color: other.border_color.or(self.border_color),
+            corner_radius: other.corner_radius.or(self.corner_radius),
+        }
+    }
+
+    /// the part of the style the children are drawn with when the platform doesn't
+    /// inherit it, the background is included since it shows behind the children
+    pub(crate) fn inherited(&self) -> Style {
+        Style {
+            color: self.color,
+            background: self.background,
+            font_family: self.font_family.clone(),
+            font_size: self.font_size,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        *self == Style::default()
+    }
+}
+
+impl From<Style> for Value {
+    fn from(style: Style) -> Value {
+        Value::from(style.to_css())
+    }
+}
+
+/// The colors and the font of a whole window, used by the widgets
+/// which don't set them in their own style
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Theme {
+    /// the color of the text
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    /// the background of the buttons and the color of the links
+    pub accent: Option<Color>,
+    pub font_family: Option<String>,
+    /// in pixels
+    pub font_size: Option<f64>,
+    /// the radius of the corners of the buttons and the text inputs, in pixels
+    pub corner_radius: Option<f64>,
+}
+
+impl Theme {
+    /// the theme declared by the attribute key, None when there is none
+    pub(crate) fn find<MSG>(key: AttribKey, attrs: &Vec<Attribute<MSG>>) -> Option<Self>
+    where
+        MSG: 'static,
+    {
+        find_value(key, attrs).map(|value| {
+            let base = Style::from_value(value);
+            let mut theme = Theme {
+                foreground: base.color,
+                background: base.background,
+                accent: None,
+                font_family: base.font_family,
+                font_size: base.font_size,
+                corner_radius: base.corner_radius,
+            };
+            for (property, value) in parse_declarations(&value.to_string()) {
+                if property == "accent-color" {
+                    theme.accent = Color::parse(&value);
+                }
+            }
+            theme
+        })
+    }
+
+    /// the dark theme of a window when the color scheme is dark and it has one,
+    /// otherwise its theme
+    pub(crate) fn for_color_scheme<MSG>(
+        attrs: &Vec<Attribute<MSG>>,
+        color_scheme: ColorScheme,
+    ) -> Option<Self>
+    where
+        MSG: 'static,
+    {
+        let dark_theme = match color_scheme {
+            ColorScheme::Dark => Theme::find(AttribKey::DarkTheme, attrs),
+            ColorScheme::Light => None,
+        };
+        dark_theme.or_else(|| Theme::find(AttribKey::Theme, attrs))
+    }
+
+    /// the style the theme gives to a kind of widget,
+    /// the style attribute of the widget takes precedence over it
+    pub(crate) fn style_for(&self, widget: &Widget) -> Style {
+        match widget {
+            Widget::Application | Widget::Window => Style {
+                color: self.foreground,
+                background: self.background,
+                font_family: self.font_family.clone(),
+                font_size: self.font_size,
+                ..Default::default()
+            },
+            Widget::Button | Widget::ToolButton | Widget::ToolToggle => Style {
+                background: self.accent,
+                corner_radius: self.corner_radius,
+                ..Default::default()
+            },
+            Widget::Link => Style {
+                color: self.accent,
+                ..Default::default()
+            },
+            Widget::TextInput | Widget::TextArea | Widget::DatePicker | Widget::TimePicker => {
+                Style {
+                    corner_radius: self.corner_radius,
+                    ..Default::default()
+                }
+            }
+            _ => Style::default(),
+        }
+    }
+}
+
+impl From<Theme> for Value {
+    fn from(theme: Theme) -> Value {
+        let base = Style {
+            color: theme.foreground,
+            background: theme.background,
+            font_family: theme.font_family,
+            font_size: theme.font_size,
+            corner_radius: theme.corner_radius,
+            ..Default::default()
+        };
+        let mut css = base.to_css();
+        if let Some(accent) = theme.accent {
+            css = format!("{} accent-color: {};", css, accent);
+        }
+        Value::from(css.trim().to_string())
+    }
+}
+
+/// Whether the platform prefers dark text on a light background or light text
+/// on a dark background
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorScheme {
+    Light,
+    Dark,
+}
+
+impl ColorScheme {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            ColorScheme::Light => "light",
+            ColorScheme::Dark => "dark",
+        }
+    }
+
+    /// the value of the InputEvent emitted to on_color_scheme: `light` or `dark`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "light" => Some(ColorScheme::Light),
+            "dark" => Some(ColorScheme::Dark),
+            _ => None,
+        }
+    }
+}
+
+impl From<ColorScheme> for Value {
+    fn from(color_scheme: ColorScheme) -> Value {
+        Value::from(color_scheme.as_str())
+    }
+}
+
+/// the property and value pairs of `property: value;` declarations
+fn parse_declarations(css: &str) -> Vec<(String, String)> {
+    css.split(';')
+        .filter_map(|declaration| {
+            let mut parts = declaration.splitn(2, ':');
+            let property = parts.next()?.trim().to_lowercase();
+            let value = parts.next()?.trim().to_string();
+            if property.is_empty() || value.is_empty() {
+                None
+            } else {
+                Some((property, value))
+            }
+        })
+        .collect()
+}
+
+/// a number of pixels, with or without the `px` unit
+fn parse_pixels(value: &str) -> Option<f64> {
+    value.trim().trim_end_matches("px").trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::attribute::{dark_theme, theme};
+
+    #[test]
+    fn parse_hex_colors() {
+        assert_eq!(Color::parse("#f80"), Some(Color::rgb(255, 136, 0)));
+        assert_eq!(Color::parse(" #ff8800 "), Some(Color::rgb(255, 136, 0)));
+        assert_eq!(
+            Color::parse("#ff880080"),
+            Some(Color::rgba(255, 136, 0, 128))
+        );
+        assert_eq!(Color::parse("#ff88"), None);
+        assert_eq!(Color::parse("#gg8800"), None);
+    }
+
+    #[test]
+    fn parse_css_colors() {
+        assert_eq!(
+            Color::parse("rgb(10, 20, 30)"),
+            Some(Color::rgb(10, 20, 30))
+        );
+        assert_eq!(
+            Color::parse("rgba(10,20,30,0.5)"),
+            Some(Color::rgba(10, 20, 30, 128))
+        );
+        assert_eq!(Color::parse("rgb(10, 20)"), None);
+        assert_eq!(Color::parse("rgb(10, 20, 300)"), None);
+        assert_eq!(Color::parse("red"), None);
+    }
+
+    #[test]
+    fn color_round_trip() {
+        let colors = [
+            Color::rgb(1, 2, 3),
+            Color::rgba(255, 136, 0, 128),
+            Color::rgba(0, 0, 0, 1),
+            Color::rgba(0, 0, 0, 0),
+        ];
+        for color in colors.iter() {
+            assert_eq!(Color::parse(&color.to_string()), Some(*color));
+        }
+    }
+
+    #[test]
+    fn style_round_trip() {
+        let style = Style {
+            color: Some(Color::rgb(0x11, 0x22, 0x33)),
+            background: Some(Color::rgba(255, 255, 255, 128)),
+            font_family: Some("monospace".to_string()),
+            font_size: Some(14.0),
+            border_width: Some(1.0),
+            border_color: Some(Color::rgb(0, 0, 0)),
+            corner_radius: Some(4.5),
+        };
+        assert_eq!(Style::from_value(&Value::from(style.clone())), style);
+    }
+
+    #[test]
+    fn style_ignores_unknown_properties() {
+        let style = Style::from_value(&Value::from("margin: 4px; font-size: 12; color: nope;"));
+        assert_eq!(
+            style,
+            Style {
+                font_size: Some(12.0),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn merge_prefers_the_other_style() {
+        let base = Style {
+            color: Some(Color::rgb(1, 1, 1)),
+            font_size: Some(12.0),
+            ..Default::default()
+        };
+        let other = Style {
+            color: Some(Color::rgb(2, 2, 2)),
+            ..Default::default()
+        };
+        assert_eq!(
+            base.merge(&other),
+            Style {
+                color: Some(Color::rgb(2, 2, 2)),
+                font_size: Some(12.0),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn theme_round_trip() {
+        let expected = Theme {
+            foreground: Some(Color::rgb(0x20, 0x20, 0x20)),
+            background: Some(Color::rgb(0xfa, 0xfa, 0xfa)),
+            accent: Some(Color::rgb(0x33, 0x66, 0xcc)),
+            font_family: Some("sans".to_string()),
+            font_size: Some(13.0),
+            corner_radius: Some(3.0),
+        };
+        let attrs: Vec<Attribute<()>> = vec![theme(expected.clone())];
+        assert_eq!(Theme::find(AttribKey::Theme, &attrs), Some(expected));
+    }
+
+    #[test]
+    fn dark_theme_for_the_dark_color_scheme() {
+        let light = Theme {
+            background: Some(Color::rgb(255, 255, 255)),
+            ..Default::default()
+        };
+        let dark = Theme {
+            background: Some(Color::rgb(0, 0, 0)),
+            ..Default::default()
+        };
+        let attrs: Vec<Attribute<()>> = vec![theme(light.clone()), dark_theme(dark.clone())];
+        assert_eq!(
+            Theme::for_color_scheme(&attrs, ColorScheme::Dark),
+            Some(dark)
+        );
+        assert_eq!(
+            Theme::for_color_scheme(&attrs, ColorScheme::Light),
+            Some(light.clone())
+        );
+        let attrs: Vec<Attribute<()>> = vec![theme(light.clone())];
+        assert_eq!(
+            Theme::for_color_scheme(&attrs, ColorScheme::Dark),
+            Some(light)
+        );
+    }
+}
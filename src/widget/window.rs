@@ -0,0 +1,93 @@
+use crate::{widget::attribute::find_value, AttribKey, Attribute, Node, Widget};
+
+/// The window attributes declared in the root node of the view
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct WindowConfig {
+    pub(crate) title: Option<String>,
+    pub(crate) width: Option<f64>,
+    pub(crate) height: Option<f64>,
+    pub(crate) min_width: Option<f64>,
+    pub(crate) min_height: Option<f64>,
+    pub(crate) resizable: Option<bool>,
+    pub(crate) icon: Option<String>,
+    pub(crate) application_id: Option<String>,
+}
+
+impl WindowConfig {
+    pub(crate) fn from_attrs<MSG>(attrs: &Vec<Attribute<MSG>>) -> Self
+    where
+        MSG: 'static,
+    {
+        let find_string = |key| find_value(key, attrs).map(|v| v.to_string());
+        let find_f64 = |key| find_value(key, attrs).map(|v| v.as_f64()).flatten();
+        WindowConfig {
+            title: find_string(AttribKey::Title),
+            width: find_f64(AttribKey::Width),
+            height: find_f64(AttribKey::Height),
+            min_width: find_f64(AttribKey::MinWidth),
+            min_height: find_f64(AttribKey::MinHeight),
+            resizable: find_value(AttribKey::Resizable, attrs)
+                .map(|v| v.as_bool())
+                .flatten(),
+            icon: find_string(AttribKey::Icon),
+            application_id: find_string(AttribKey::ApplicationId),
+        }
+    }
+
+    /// the window configuration of the view, if the root node is a window
+    pub(crate) fn from_root<MSG>(root: &Node<MSG>) -> Option<Self>
+    where
+        MSG: 'static,
+    {
+        root.as_element_ref()
+            .filter(|element| element.tag == Widget::Window)
+            .map(|element| Self::from_attrs(&element.attrs))
+    }
+}
+
+/// The application id declared in the root node of the view, either in the
+/// application or in the window
+pub(crate) fn application_id<MSG>(root: &Node<MSG>) -> Option<String>
+where
+    MSG: 'static,
+{
+    root.as_element_ref()
+        .filter(|element| element.tag == Widget::Application || element.tag == Widget::Window)
+        .map(|element| find_value(AttribKey::ApplicationId, &element.attrs))
+        .flatten()
+        .map(|v| v.to_string())
+}
+
+/// The top level windows described by the view, each paired with its key.
+///
+/// If the root node is an application, each of its window children is a top level window
+/// keyed by its key attribute or else its position. Otherwise the whole view is the
+/// content of a single window with an empty key.
+pub(crate) fn window_nodes<MSG>(root: &Node<MSG>) -> Vec<(String, &Node<MSG>)>
+where
+    MSG: 'static,
+{
+    match root.as_element_ref() {
+        Some(element) if element.tag == Widget::Application => element
+            .children
+            .iter()
+            .enumerate()
+            .filter(|(_, child)| {
+                child
+                    .as_element_ref()
+                    .map(|child| child.tag == Widget::Window)
+                    .unwrap_or(false)
+            })
+            .map(|(index, child)| {
+                let key = child
+                    .as_element_ref()
+                    .map(|child| find_value(AttribKey::Key, &child.attrs))
+                    .flatten()
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| index.to_string());
+                (key, child)
+            })
+            .collect(),
+        _ => vec![(String::new(), root)],
+    }
+}